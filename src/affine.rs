@@ -0,0 +1,422 @@
+use crate::mat::*;
+use crate::num::prelude::*;
+use crate::quat::*;
+use crate::vec::*;
+use core::ops::*;
+
+macro_rules! impl_affine {
+    ($Affine:ident $Mat:ident $Vec:ident => $BigMat:ident $BigVec:ident ($($get:tt),+) $last:tt) => {
+        #[derive(Debug, Default, Clone, Hash, PartialEq)]
+        #[repr(C)]
+        pub struct $Affine<T> {
+            /// The linear part of the transform, applied before the translation.
+            pub matrix: $Mat<T>,
+            pub translation: $Vec<T>,
+        }
+
+        impl<T: Copy> Copy for $Affine<T> {}
+
+        impl<T: Eq> Eq for $Affine<T> {}
+
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for $Affine<T> {}
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: bytemuck::Pod> bytemuck::Pod for $Affine<T> {}
+
+        impl<T: Zero + One + Copy> $Affine<T> {
+            pub const IDENTITY: Self = Self {
+                matrix: $Mat::IDENTITY,
+                translation: $Vec::ZERO,
+            };
+        }
+
+        impl<T> $Affine<T> {
+            #[inline]
+            pub const fn from_mat_translation(matrix: $Mat<T>, translation: $Vec<T>) -> Self {
+                Self { matrix, translation }
+            }
+
+            /// Creates an affine transform that only applies the linear transform `matrix`.
+            #[inline]
+            pub const fn from_mat(matrix: $Mat<T>) -> Self where T: Zero {
+                Self { matrix, translation: $Vec::ZERO }
+            }
+
+            /// Creates an affine transform that only applies a translation.
+            #[inline]
+            pub const fn from_translation(translation: $Vec<T>) -> Self where T: Zero + One + Copy {
+                Self { matrix: $Mat::IDENTITY, translation }
+            }
+        }
+
+        impl<T> $Affine<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            /// Transforms the given point, applying both the linear part and the translation.
+            #[inline]
+            #[must_use]
+            pub fn transform_point(&self, rhs: $Vec<T>) -> $Vec<T> {
+                self.matrix.mul_vec(rhs) + self.translation
+            }
+
+            /// Transforms the given vector, applying only the linear part and not the
+            /// translation.
+            #[inline]
+            #[must_use]
+            pub fn transform_vector(&self, rhs: $Vec<T>) -> $Vec<T> {
+                self.matrix.mul_vec(rhs)
+            }
+
+            /// Composes `self` and `rhs` into a single transform, which applies `rhs` first and
+            /// `self` second.
+            #[inline]
+            #[must_use]
+            pub fn mul_affine(&self, rhs: &Self) -> Self {
+                Self {
+                    matrix: self.matrix.mul_mat(&rhs.matrix),
+                    translation: self.transform_point(rhs.translation),
+                }
+            }
+        }
+
+        impl<T> $Affine<T>
+            where T: Float + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T> + Copy
+        {
+            /// Returns the inverse of `self`.
+            ///
+            /// Only the linear part has to be inverted, which is cheaper and more precise than
+            /// inverting the equivalent homogeneous matrix. If the transform is not invertible
+            /// the result will contain non-finite values, use [`Self::try_inverse`] to check for
+            /// this.
+            #[inline]
+            #[must_use]
+            pub fn inverse(&self) -> Self {
+                let matrix = self.matrix.inverse();
+                Self {
+                    translation: -matrix.mul_vec(self.translation),
+                    matrix,
+                }
+            }
+
+            /// Returns the inverse of `self`, or `None` if the linear part is not invertible.
+            ///
+            #[doc = concat!("See [`", stringify!($Mat), "::try_inverse`] for when the linear part counts as not invertible.")]
+            #[inline]
+            #[must_use]
+            pub fn try_inverse(&self) -> Option<Self>
+                where T: Compare + Epsilon + Infinity
+            {
+                let matrix = self.matrix.try_inverse()?;
+                Some(Self {
+                    translation: -matrix.mul_vec(self.translation),
+                    matrix,
+                })
+            }
+        }
+
+        impl<T> Mul<$Affine<T>> for $Affine<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            type Output = $Affine<T>;
+            #[inline]
+            fn mul(self, rhs: $Affine<T>) -> Self::Output {
+                self.mul_affine(&rhs)
+            }
+        }
+        impl<T> MulAssign<$Affine<T>> for $Affine<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $Affine<T>) {
+                *self = self.mul_affine(&rhs);
+            }
+        }
+
+        impl<T: Zero + One + Copy> From<$Affine<T>> for $BigMat<T> {
+            /// Converts the affine transform into the equivalent homogeneous matrix.
+            #[inline]
+            fn from(value: $Affine<T>) -> Self {
+                let extend = |v: $Vec<T>, e: T| $BigVec::new($(v.$get),+, e);
+                $BigMat::from_cols(
+                    $(extend(value.matrix.$get, T::ZERO)),+,
+                    extend(value.translation, T::ONE),
+                )
+            }
+        }
+        impl<T: Copy> From<$BigMat<T>> for $Affine<T> {
+            /// Converts the homogeneous matrix into an affine transform.
+            ///
+            /// The last row of the matrix is ignored, it is expected to be `[0, .., 0, 1]`.
+            #[inline]
+            fn from(value: $BigMat<T>) -> Self {
+                let truncate = |v: $BigVec<T>| $Vec::new($(v.$get),+);
+                Self {
+                    matrix: $Mat::from_cols($(truncate(value.$get)),+),
+                    translation: truncate(value.$last),
+                }
+            }
+        }
+    };
+}
+
+impl_affine!(Affine2 Mat2 Vec2 => Mat3 Vec3 (x, y) z);
+impl_affine!(Affine3 Mat3 Vec3 => Mat4 Vec4 (x, y, z) w);
+
+impl<T> Affine2<T>
+where
+    T: Float + Mul<Output = T> + Neg<Output = T> + Copy,
+{
+    /// Creates an affine transform which scales by `scale`, then rotates counter-clockwise by
+    /// `angle` radians and finally translates by `translation`.
+    #[inline]
+    #[must_use]
+    pub fn from_scale_rotation_translation(scale: Vec2<T>, angle: T, translation: Vec2<T>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            matrix: Mat2::new(cos * scale.x, sin * scale.x, -sin * scale.y, cos * scale.y),
+            translation,
+        }
+    }
+}
+
+impl<T> Affine3<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates an affine transform which scales by `scale`, then rotates by `rotation` and
+    /// finally translates by `translation`.
+    ///
+    /// The rotation must be normalized.
+    #[inline]
+    #[must_use]
+    pub fn from_scale_rotation_translation(
+        scale: Vec3<T>,
+        rotation: Quat<T>,
+        translation: Vec3<T>,
+    ) -> Self {
+        let rotation = rotation.to_mat3();
+        Self {
+            matrix: Mat3::from_cols(
+                rotation.x * scale.x,
+                rotation.y * scale.y,
+                rotation.z * scale.z,
+            ),
+            translation,
+        }
+    }
+}
+
+impl<T> Mat4<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates a transform which scales by `scale`, then rotates by `rotation` and finally
+    /// translates by `translation`.
+    ///
+    /// The rotation must be normalized.
+    #[inline]
+    #[must_use]
+    pub fn from_scale_rotation_translation(
+        scale: Vec3<T>,
+        rotation: Quat<T>,
+        translation: Vec3<T>,
+    ) -> Self {
+        Affine3::from_scale_rotation_translation(scale, rotation, translation).into()
+    }
+
+    /// Decomposes the affine transform `self` into its scale, rotation and translation.
+    ///
+    /// If the transform mirrors (has a negative determinant), the `x` scale is negated so the
+    /// rotation stays a proper rotation. If a scale is zero, the rotation axis it would have
+    /// come from is reconstructed to be orthogonal to the remaining axes. The last row of `self`
+    /// is ignored and shear is not supported.
+    #[must_use]
+    pub fn to_scale_rotation_translation(&self) -> (Vec3<T>, Quat<T>, Vec3<T>) {
+        let Affine3 {
+            matrix,
+            translation,
+        } = Affine3::from(*self);
+
+        let mut scale = Vec3::new(matrix.x.length(), matrix.y.length(), matrix.z.length());
+        if matrix.determinant() < T::ZERO {
+            scale.x = -scale.x;
+        }
+
+        let axis = |v: Vec3<T>, scale: T| {
+            let inv_scale = T::ONE / scale;
+            inv_scale.is_finite().then(|| v * inv_scale)
+        };
+        let (x, y, z) = match (
+            axis(matrix.x, scale.x),
+            axis(matrix.y, scale.y),
+            axis(matrix.z, scale.z),
+        ) {
+            (Some(x), Some(y), Some(z)) => (x, y, z),
+            (None, Some(y), Some(z)) => (y.cross(z).normalize(), y, z),
+            (Some(x), None, Some(z)) => (x, z.cross(x).normalize(), z),
+            (Some(x), Some(y), None) => (x, y, x.cross(y).normalize()),
+            (Some(x), None, None) => {
+                let y = x.any_orthonormal_vector();
+                (x, y, x.cross(y))
+            }
+            (None, Some(y), None) => {
+                let z = y.any_orthonormal_vector();
+                (y.cross(z), y, z)
+            }
+            (None, None, Some(z)) => {
+                let x = z.any_orthonormal_vector();
+                (x, z.cross(x), z)
+            }
+            (None, None, None) => (Vec3::X, Vec3::Y, Vec3::Z),
+        };
+        let rotation = Quat::from_mat3(&Mat3::from_cols(x, y, z)).normalize();
+
+        (scale, rotation, translation)
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod affine_test {
+    use super::*;
+    use core::f64::consts::FRAC_PI_2;
+
+    fn assert_approx_eq(a: &[f64], b: &[f64]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn affine2_transform() {
+        let a = Affine2::from_scale_rotation_translation(
+            Vec2::new(2.0, 3.0),
+            FRAC_PI_2,
+            Vec2::new(10.0, 20.0),
+        );
+        assert_approx_eq(&a.transform_point(Vec2::new(1.0, 1.0)), &[7.0, 22.0]);
+        assert_approx_eq(&a.transform_vector(Vec2::new(1.0, 1.0)), &[-3.0, 2.0]);
+
+        let m = Mat3::from(a);
+        assert_approx_eq(&(m * Vec3::new(1.0, 1.0, 1.0)), &[7.0, 22.0, 1.0]);
+        assert_eq!(Affine2::from(m), a);
+    }
+
+    #[test]
+    fn affine3_compose() {
+        let a = Affine3::from_scale_rotation_translation(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quat::from_axis_angle(Vec3::Y, 0.5),
+            Vec3::new(4.0, 5.0, 6.0),
+        );
+        let b = Affine3::from_scale_rotation_translation(
+            Vec3::splat(0.5),
+            Quat::from_axis_angle(Vec3::X, -1.0),
+            Vec3::new(-1.0, 0.0, 1.0),
+        );
+        let p = Vec3::new(0.3, -0.7, 2.0);
+        assert_approx_eq(
+            &(a * b).transform_point(p),
+            &a.transform_point(b.transform_point(p)),
+        );
+        assert_eq!(Mat4::from(a * b), Mat4::from(a) * Mat4::from(b));
+        assert_eq!(Affine3::from(Mat4::from(a)), a);
+    }
+
+    #[test]
+    fn affine3_inverse() {
+        let a = Affine3::from_scale_rotation_translation(
+            Vec3::new(1.0, 2.0, 4.0),
+            Quat::from_axis_angle(Vec3::Z, 1.2),
+            Vec3::new(4.0, 5.0, 6.0),
+        );
+        let p = Vec3::new(1.0, 2.0, 3.0);
+        assert_approx_eq(&a.inverse().transform_point(a.transform_point(p)), &p);
+
+        let identity = Mat4::from(a * a.inverse());
+        for (col, expected) in [identity.x, identity.y, identity.z, identity.w]
+            .iter()
+            .zip([Vec4::X, Vec4::Y, Vec4::Z, Vec4::W])
+        {
+            assert_approx_eq(col, &expected);
+        }
+
+        let singular = Affine3::from_mat(Mat3::from_diagonal(Vec3::new(1.0, 0.0, 1.0)));
+        assert_eq!(singular.try_inverse(), None);
+    }
+
+    fn assert_mat_approx_eq(a: Mat4<f64>, b: Mat4<f64>) {
+        assert_approx_eq(&a.x, &b.x);
+        assert_approx_eq(&a.y, &b.y);
+        assert_approx_eq(&a.z, &b.z);
+        assert_approx_eq(&a.w, &b.w);
+    }
+
+    #[test]
+    fn mat4_scale_rotation_translation() {
+        let rotation = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 2.0) / 3.0, 0.8);
+        let translation = Vec3::new(4.0, 5.0, 6.0);
+        let scale = Vec3::new(0.5, 2.0, 3.0);
+        let m = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+
+        let (s, r, t) = m.to_scale_rotation_translation();
+        assert_approx_eq(&s, &scale);
+        assert_approx_eq(&r, &rotation);
+        assert_approx_eq(&t, &translation);
+    }
+
+    #[test]
+    fn mat4_scale_rotation_translation_mirrored() {
+        let rotation = Quat::from_axis_angle(Vec3::Y, -2.0);
+        let translation = Vec3::new(-1.0, 0.0, 1.0);
+        for scale in [
+            Vec3::new(-1.0, 2.0, 3.0),
+            Vec3::new(1.0, -2.0, 3.0),
+            Vec3::new(-1.0, -2.0, -3.0),
+        ] {
+            let m = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+            let (s, r, t) = m.to_scale_rotation_translation();
+            assert!(s.x < 0.0);
+            assert_approx_eq(&[r.length()], &[1.0]);
+            assert_mat_approx_eq(Mat4::from_scale_rotation_translation(s, r, t), m);
+        }
+    }
+
+    #[test]
+    fn mat4_scale_rotation_translation_degenerate() {
+        let rotation = Quat::from_axis_angle(Vec3::new(0.0, 0.6, 0.8), 1.0);
+        let translation = Vec3::new(1.0, 2.0, 3.0);
+        for scale in [
+            Vec3::new(0.0, 2.0, 3.0),
+            Vec3::new(1.0, 0.0, 3.0),
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+            Vec3::new(0.0, 0.0, 3.0),
+            Vec3::ZERO,
+        ] {
+            let m = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+            let (s, r, t) = m.to_scale_rotation_translation();
+            assert_approx_eq(&s, &scale);
+            assert_approx_eq(&[r.length()], &[1.0]);
+            assert_approx_eq(&t, &translation);
+            assert_mat_approx_eq(Mat4::from_scale_rotation_translation(s, r, t), m);
+        }
+    }
+}
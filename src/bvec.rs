@@ -0,0 +1,133 @@
+use core::ops::*;
+
+macro_rules! impl_bvec {
+    ($BVec:ident, $size:tt, ($($get:tt),+), ($($index:tt),+)) => {
+        /// A vector of masks, as returned by the component-wise comparisons of vectors.
+        ///
+        /// The mask type `M` is `bool` for primitive elements. For vectors of `core::simd::Simd`
+        /// lanes it is the matching `core::simd::Mask`, so every lane is tracked on its own.
+        #[derive(Debug, Default, Clone, Hash, PartialEq)]
+        #[repr(C)]
+        pub struct $BVec<M = bool> {
+            $(pub $get: M),+
+        }
+
+        impl<M: Copy> Copy for $BVec<M> {}
+
+        impl<M: Eq> Eq for $BVec<M> {}
+
+        impl $BVec<bool> {
+            pub const FALSE: Self = Self { $($get: false),+ };
+            pub const TRUE: Self = Self { $($get: true),+ };
+
+            /// Returns a bitmask with the lowest bits set to the elements of `self`, `x` being
+            /// the least significant bit.
+            #[inline]
+            pub fn bitmask(self) -> u32 {
+                $((self.$get as u32) << $index)|+
+            }
+        }
+
+        impl<M> $BVec<M> {
+            pub const fn new($($get: M),+) -> Self {
+                Self { $($get),+ }
+            }
+            pub const fn splat(v: M) -> Self where M: Copy {
+                Self { $($get: v),+ }
+            }
+
+            pub fn into_array(self) -> [M; $size] {
+                [$(self.$get),+]
+            }
+
+            /// Returns whether any element of `self` is set.
+            ///
+            /// For SIMD masks this is evaluated per lane.
+            #[inline]
+            pub fn any(self) -> M where M: BitOr<Output = M> {
+                reduce_op!(|, $(self.$get),+)
+            }
+            /// Returns whether all elements of `self` are set.
+            ///
+            /// For SIMD masks this is evaluated per lane.
+            #[inline]
+            pub fn all(self) -> M where M: BitAnd<Output = M> {
+                reduce_op!(&, $(self.$get),+)
+            }
+        }
+
+        impl<M: BitAnd<Output = M>> BitAnd for $BVec<M> {
+            type Output = Self;
+            #[inline]
+            fn bitand(self, rhs: Self) -> Self {
+                Self { $($get: self.$get & rhs.$get),+ }
+            }
+        }
+        impl<M: BitOr<Output = M>> BitOr for $BVec<M> {
+            type Output = Self;
+            #[inline]
+            fn bitor(self, rhs: Self) -> Self {
+                Self { $($get: self.$get | rhs.$get),+ }
+            }
+        }
+        impl<M: BitXor<Output = M>> BitXor for $BVec<M> {
+            type Output = Self;
+            #[inline]
+            fn bitxor(self, rhs: Self) -> Self {
+                Self { $($get: self.$get ^ rhs.$get),+ }
+            }
+        }
+        impl<M: Not<Output = M>> Not for $BVec<M> {
+            type Output = Self;
+            #[inline]
+            fn not(self) -> Self {
+                Self { $($get: !self.$get),+ }
+            }
+        }
+
+        impl<M> From<[M; $size]> for $BVec<M> {
+            #[inline]
+            fn from(value: [M; $size]) -> Self {
+                let [$($get),+] = value;
+                Self { $($get),+ }
+            }
+        }
+        impl<M> From<$BVec<M>> for [M; $size] {
+            #[inline]
+            fn from(value: $BVec<M>) -> Self {
+                value.into_array()
+            }
+        }
+    };
+}
+
+impl_bvec!(BVec2, 2, (x, y), (0, 1));
+impl_bvec!(BVec3, 3, (x, y, z), (0, 1, 2));
+impl_bvec!(BVec4, 4, (x, y, z, w), (0, 1, 2, 3));
+
+#[cfg(test)]
+mod bvec_test {
+    use super::*;
+
+    #[test]
+    fn bvec_any_all_bitmask() {
+        let a = BVec3::new(true, false, true);
+        assert!(a.any());
+        assert!(!a.all());
+        assert!(BVec3::TRUE.all());
+        assert!(!BVec3::FALSE.any());
+        assert_eq!(a.bitmask(), 0b101);
+        assert_eq!(BVec4::TRUE.bitmask(), 0b1111);
+        assert_eq!(BVec2::new(false, true).bitmask(), 0b10);
+    }
+
+    #[test]
+    fn bvec_ops() {
+        let a = BVec2::new(true, false);
+        let b = BVec2::new(true, true);
+        assert_eq!(a & b, BVec2::new(true, false));
+        assert_eq!(a | b, BVec2::TRUE);
+        assert_eq!(a ^ b, BVec2::new(false, true));
+        assert_eq!(!a, BVec2::new(false, true));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::mat::*;
+use crate::num::prelude::*;
+use crate::quat::*;
+use crate::vec::*;
+use core::ops::*;
+
+/// Euler rotation sequences.
+///
+/// Each variant names the axes of three intrinsic rotations in the order they are applied. For
+/// example `XYZ` rotates around `X` first, then around the rotated `Y` axis and finally around the
+/// twice rotated `Z` axis. This is equivalent to the matrix product `Rx * Ry * Rz`, or the
+/// extrinsic sequence `ZYX`.
+///
+/// The first six variants are Tait-Bryan angles which use three different axes, the last six are
+/// proper Euler angles which use the same axis for the first and last rotation.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum EulerRot {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+impl EulerRot {
+    /// Returns the indices of the three rotation axes in the order they are applied.
+    #[inline]
+    const fn axes(self) -> [usize; 3] {
+        match self {
+            Self::XYZ => [0, 1, 2],
+            Self::XZY => [0, 2, 1],
+            Self::YXZ => [1, 0, 2],
+            Self::YZX => [1, 2, 0],
+            Self::ZXY => [2, 0, 1],
+            Self::ZYX => [2, 1, 0],
+
+            Self::XYX => [0, 1, 0],
+            Self::XZX => [0, 2, 0],
+            Self::YXY => [1, 0, 1],
+            Self::YZY => [1, 2, 1],
+            Self::ZXZ => [2, 0, 2],
+            Self::ZYZ => [2, 1, 2],
+        }
+    }
+
+    /// Returns `true` if this is a proper Euler sequence, where the first and last axis are the
+    /// same.
+    #[inline]
+    pub const fn is_proper(self) -> bool {
+        let [i, _, k] = self.axes();
+        i == k
+    }
+}
+
+impl<T> Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates a quaternion from the three Euler angles `a`, `b` and `c` in radians, applied
+    /// in the given `order`.
+    #[must_use]
+    pub fn from_euler(order: EulerRot, a: T, b: T, c: T) -> Self {
+        let axes = [Vec3::X, Vec3::Y, Vec3::Z];
+        let [i, j, k] = order.axes();
+        Self::from_axis_angle(axes[i], a)
+            * Self::from_axis_angle(axes[j], b)
+            * Self::from_axis_angle(axes[k], c)
+    }
+}
+
+impl<T> Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Epsilon
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Returns the Euler angles of `self` in the given `order`.
+    ///
+    /// See [`Mat3::to_euler`] for the ranges of the returned angles and the behavior in gimbal
+    /// lock.
+    #[inline]
+    #[must_use]
+    pub fn to_euler(self, order: EulerRot) -> (T, T, T) {
+        self.to_mat3().to_euler(order)
+    }
+}
+
+impl<T> Mat3<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates a rotation matrix from the three Euler angles `a`, `b` and `c` in radians,
+    /// applied in the given `order`.
+    #[inline]
+    #[must_use]
+    pub fn from_euler(order: EulerRot, a: T, b: T, c: T) -> Self {
+        Quat::from_euler(order, a, b, c).to_mat3()
+    }
+}
+
+impl<T> Mat3<T>
+where
+    T: Float + Zero + One + Epsilon + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + Copy,
+{
+    /// Returns the Euler angles `(a, b, c)` of the rotation matrix `self` in the given `order`.
+    ///
+    /// The first and last angle are in `[-pi, pi]`. The middle angle is in `[-pi/2, pi/2]` for
+    /// Tait-Bryan sequences and in `[0, pi]` for proper Euler sequences.
+    ///
+    /// When the middle angle is at the edge of its range (gimbal lock) the first and last axis
+    /// line up and only their combined rotation can be recovered. In that case the last angle is
+    /// always `0` and the whole rotation is attributed to the first angle.
+    #[must_use]
+    pub fn to_euler(&self, order: EulerRot) -> (T, T, T) {
+        let [i, j, _] = order.axes();
+        let k = 3 - i - j;
+        // Flips the sign of the matrix elements for sequences that are odd permutations of XYZ.
+        let even = (i + 1) % 3 == j;
+        let s = |v: T| if even { v } else { -v };
+        let cols = [self.x, self.y, self.z];
+        let m = |row: usize, col: usize| cols[col][row];
+
+        let two = T::ONE + T::ONE;
+        let threshold = T::EPSILON * two * two * two * two;
+        if order.is_proper() {
+            let sin_b = (m(i, j) * m(i, j) + m(i, k) * m(i, k)).sqrt();
+            let b = sin_b.atan2(m(i, i));
+            if sin_b > threshold {
+                let a = m(j, i).atan2(-s(m(k, i)));
+                let c = m(i, j).atan2(s(m(i, k)));
+                (a, b, c)
+            } else {
+                (s(m(k, j)).atan2(m(j, j)), b, T::ZERO)
+            }
+        } else {
+            let cos_b = (m(i, i) * m(i, i) + m(i, j) * m(i, j)).sqrt();
+            let b = s(m(i, k)).atan2(cos_b);
+            if cos_b > threshold {
+                let a = (-s(m(j, k))).atan2(m(k, k));
+                let c = (-s(m(i, j))).atan2(m(i, i));
+                (a, b, c)
+            } else {
+                (s(m(k, j)).atan2(m(j, j)), b, T::ZERO)
+            }
+        }
+    }
+}
+
+impl<T> Mat4<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates a rotation matrix from the three Euler angles `a`, `b` and `c` in radians,
+    /// applied in the given `order`.
+    #[inline]
+    #[must_use]
+    pub fn from_euler(order: EulerRot, a: T, b: T, c: T) -> Self {
+        Quat::from_euler(order, a, b, c).to_mat4()
+    }
+}
+
+impl<T> Mat4<T>
+where
+    T: Float + Zero + One + Epsilon + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + Copy,
+{
+    /// Returns the Euler angles of the upper 3x3 rotation part of `self` in the given `order`.
+    ///
+    /// See [`Mat3::to_euler`] for the ranges of the returned angles and the behavior in gimbal
+    /// lock.
+    #[inline]
+    #[must_use]
+    pub fn to_euler(&self, order: EulerRot) -> (T, T, T) {
+        Mat3::from(*self).to_euler(order)
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod euler_test {
+    use super::*;
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    const ORDERS: [EulerRot; 12] = [
+        EulerRot::XYZ,
+        EulerRot::XZY,
+        EulerRot::YXZ,
+        EulerRot::YZX,
+        EulerRot::ZXY,
+        EulerRot::ZYX,
+        EulerRot::XYX,
+        EulerRot::XZX,
+        EulerRot::YXY,
+        EulerRot::YZY,
+        EulerRot::ZXZ,
+        EulerRot::ZYZ,
+    ];
+
+    fn assert_approx_eq(a: &[f64], b: &[f64]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9),
+            "{a:?} != {b:?}"
+        );
+    }
+    fn assert_mat_approx_eq(a: Mat3<f64>, b: Mat3<f64>) {
+        assert_approx_eq(&a.x, &b.x);
+        assert_approx_eq(&a.y, &b.y);
+        assert_approx_eq(&a.z, &b.z);
+    }
+
+    #[test]
+    fn euler_matches_axis_rotations() {
+        let (a, b, c) = (0.1, 0.2, 0.3);
+        let rx = |t| Mat3::from_euler(EulerRot::XYX, t, 0.0, 0.0);
+        let ry = |t| Mat3::from_euler(EulerRot::YXY, t, 0.0, 0.0);
+        let rz = |t| Mat3::from_euler(EulerRot::ZXZ, t, 0.0, 0.0);
+        assert_mat_approx_eq(
+            Mat3::from_euler(EulerRot::XYZ, a, b, c),
+            rx(a) * ry(b) * rz(c),
+        );
+        assert_mat_approx_eq(
+            Mat3::from_euler(EulerRot::ZYX, a, b, c),
+            rz(a) * ry(b) * rx(c),
+        );
+        assert_mat_approx_eq(
+            Mat3::from_euler(EulerRot::YZY, a, b, c),
+            ry(a) * rz(b) * ry(c),
+        );
+        assert_approx_eq(&rz(FRAC_PI_2).mul_vec(Vec3::X), &Vec3::Y);
+    }
+
+    #[test]
+    fn euler_round_trip() {
+        for order in ORDERS {
+            let middle = if order.is_proper() {
+                [0.1, 0.7, 1.5, 2.0, 3.0]
+            } else {
+                [-1.5, -0.7, 0.0, 0.7, 1.5]
+            };
+            for a in [-3.0, -1.0, 0.0, 0.5, 2.5] {
+                for b in middle {
+                    for c in [-2.5, -0.5, 0.0, 1.0, 3.0] {
+                        let q = Quat::from_euler(order, a, b, c);
+                        let (ea, eb, ec) = q.to_euler(order);
+                        assert_approx_eq(&[ea, eb, ec], &[a, b, c]);
+
+                        let m = Mat4::from_euler(order, a, b, c);
+                        let (ea, eb, ec) = m.to_euler(order);
+                        assert_approx_eq(&[ea, eb, ec], &[a, b, c]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn euler_gimbal_lock() {
+        for order in ORDERS {
+            let locked = if order.is_proper() {
+                [0.0, PI]
+            } else {
+                [-FRAC_PI_2, FRAC_PI_2]
+            };
+            for b in locked {
+                let m = Mat3::from_euler(order, 0.3, b, 0.2);
+                let (ea, eb, ec) = m.to_euler(order);
+                assert_approx_eq(&[eb, ec], &[b, 0.0]);
+                assert_mat_approx_eq(Mat3::from_euler(order, ea, eb, ec), m);
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Hooks for the operations that have SIMD fast paths.
+//!
+//! Every vector and matrix type implements these traits with generic element-wise code. With the
+//! `simd` feature those implementations are `default`, so `simd.rs` can specialize them for
+//! concrete types such as `Vec4<f32>` without changing the generic public API.
+
+use crate::num::prelude::*;
+use crate::vec::Vec3;
+use core::ops::*;
+
+pub(crate) trait VecKernel<T>: Sized {
+    fn add(self, rhs: Self) -> Self
+    where
+        T: Add<Output = T>;
+    fn sub(self, rhs: Self) -> Self
+    where
+        T: Sub<Output = T>;
+    fn mul(self, rhs: Self) -> Self
+    where
+        T: Mul<Output = T>;
+    fn div(self, rhs: Self) -> Self
+    where
+        T: Div<Output = T>;
+    fn dot(self, rhs: Self) -> T
+    where
+        T: Add<Output = T> + Mul<Output = T>;
+}
+
+pub(crate) trait MatKernel<T>: Sized {
+    fn mul_mat(&self, rhs: &Self) -> Self
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy;
+    fn inverse(&self) -> Self
+    where
+        T: Float
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + Copy;
+}
+
+pub(crate) trait TransformKernel<T> {
+    fn transform_points(&self, points: &mut [Vec3<T>])
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy;
+    fn transform_vectors(&self, vectors: &mut [Vec3<T>])
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy;
+}
@@ -1,14 +1,37 @@
-#![cfg_attr(feature = "simd", feature(portable_simd))]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd, min_specialization))]
 
+#[macro_use]
+mod macros;
+
+mod kernel;
+#[cfg(all(feature = "libm", not(feature = "std"), not(feature = "num-traits")))]
+mod libm;
 #[cfg(feature = "num-traits")]
 mod numtraits;
-#[cfg(feature = "simd")]
-mod simd;
 
-mod num;
+pub mod num;
 
+pub mod affine;
+pub mod bvec;
+pub mod euler;
 pub mod mat;
+pub mod projection;
+pub mod quat;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod smat;
+pub mod svec;
+#[cfg(feature = "swizzles")]
+mod swizzles;
 pub mod vec;
 
+pub use affine::*;
+pub use bvec::*;
+pub use euler::*;
 pub use mat::*;
+pub use projection::*;
+pub use quat::*;
+pub use smat::*;
+pub use svec::*;
 pub use vec::*;
@@ -0,0 +1,86 @@
+use crate::num::prelude::*;
+
+/// Implements [`Float`] for a primitive float type using the functions provided by `libm`.
+macro_rules! derive_float_libm {
+    ($($type:ty {
+        $sqrt:ident $hypot:ident $floor:ident $ceil:ident $round:ident $trunc:ident
+        $fabs:ident $copysign:ident $fma:ident
+        $sin:ident $cos:ident $tan:ident $sincos:ident $asin:ident $acos:ident $atan:ident $atan2:ident
+        $exp:ident $log:ident $pow:ident
+    })+) => {
+        $(
+            impl Float for $type {
+                #[inline(always)]
+                fn sqrt(self) -> Self { libm::$sqrt(self) }
+                #[inline(always)]
+                fn hypot(self, other: Self) -> Self { libm::$hypot(self, other) }
+
+                #[inline(always)]
+                fn floor(self) -> Self { libm::$floor(self) }
+                #[inline(always)]
+                fn ceil(self) -> Self { libm::$ceil(self) }
+                #[inline(always)]
+                fn round(self) -> Self { libm::$round(self) }
+                #[inline(always)]
+                fn trunc(self) -> Self { libm::$trunc(self) }
+                #[inline(always)]
+                fn fract(self) -> Self { self - libm::$trunc(self) }
+
+                #[inline(always)]
+                fn abs(self) -> Self { libm::$fabs(self) }
+                #[inline(always)]
+                fn signum(self) -> Self {
+                    if self.is_nan() { self } else { libm::$copysign(1.0, self) }
+                }
+                #[inline(always)]
+                fn copysign(self, sign: Self) -> Self { libm::$copysign(self, sign) }
+                #[inline(always)]
+                fn recip(self) -> Self { 1.0 / self }
+                #[inline(always)]
+                fn mul_add(self, a: Self, b: Self) -> Self { libm::$fma(self, a, b) }
+
+                #[inline(always)]
+                fn sin(self) -> Self { libm::$sin(self) }
+                #[inline(always)]
+                fn cos(self) -> Self { libm::$cos(self) }
+                #[inline(always)]
+                fn tan(self) -> Self { libm::$tan(self) }
+                #[inline(always)]
+                fn sin_cos(self) -> (Self, Self) { libm::$sincos(self) }
+                #[inline(always)]
+                fn asin(self) -> Self { libm::$asin(self) }
+                #[inline(always)]
+                fn acos(self) -> Self { libm::$acos(self) }
+                #[inline(always)]
+                fn atan(self) -> Self { libm::$atan(self) }
+                #[inline(always)]
+                fn atan2(self, other: Self) -> Self { libm::$atan2(self, other) }
+
+                #[inline(always)]
+                fn exp(self) -> Self { libm::$exp(self) }
+                #[inline(always)]
+                fn ln(self) -> Self { libm::$log(self) }
+                #[inline(always)]
+                fn powf(self, n: Self) -> Self { libm::$pow(self, n) }
+                #[inline(always)]
+                fn powi(self, n: i32) -> Self { libm::$pow(self, n as Self) }
+
+                #[inline(always)]
+                fn is_nan(self) -> bool { self.is_nan() }
+                #[inline(always)]
+                fn is_finite(self) -> bool { self.is_finite() }
+            }
+        )+
+    };
+}
+
+derive_float_libm!(
+    f32 {
+        sqrtf hypotf floorf ceilf roundf truncf fabsf copysignf fmaf
+        sinf cosf tanf sincosf asinf acosf atanf atan2f expf logf powf
+    }
+    f64 {
+        sqrt hypot floor ceil round trunc fabs copysign fma
+        sin cos tan sincos asin acos atan atan2 exp log pow
+    }
+);
@@ -0,0 +1,223 @@
+macro_rules! reduce_fn {
+    ($fn:expr, $a:expr, $b:expr) => { $fn($a, $b) };
+    ($fn:expr, $a:expr, $b:expr, $($v:expr),+) => { reduce_fn!($fn, reduce_fn!($fn, $a, $b), $($v),+) };
+}
+macro_rules! reduce_op {
+    ($op:tt, $a:expr, $b:expr) => { $a $op $b };
+    ($op:tt, $a:expr, $b:expr, $($v:expr),+) => { reduce_op!($op, reduce_op!($op, $a, $b), $($v),+) };
+}
+
+/// Marks a function in a trait impl as `default`, so it can be specialized by the SIMD fast
+/// paths when the `simd` feature is enabled.
+#[cfg(feature = "simd")]
+macro_rules! specializable {
+    ($(#[$attr:meta])* fn $($rest:tt)*) => { $(#[$attr])* default fn $($rest)* };
+}
+#[cfg(not(feature = "simd"))]
+macro_rules! specializable {
+    ($(#[$attr:meta])* fn $($rest:tt)*) => { $(#[$attr])* fn $($rest)* };
+}
+
+/// Implements the conversions between element types for a vector or matrix type: `as_*` methods,
+/// `from_lossless` and `TryFrom` with [`CheckedCast`](crate::num::CheckedCast).
+// NOTE: Lossless conversions are `from_lossless` rather than `From` impls, the operators accept
+// any `Into<$Type<T>>` so those would make mixed element types and untyped literals ambiguous.
+macro_rules! impl_cast {
+    ($Type:ident ($($get:tt),+)) => {
+        impl<T> $Type<T> {
+            impl_cast!(@as $Type
+                as_usize usize, as_u8 u8, as_u16 u16, as_u32 u32, as_u64 u64, as_u128 u128,
+                as_isize isize, as_i8 i8, as_i16 i16, as_i32 i32, as_i64 i64, as_i128 i128,
+                as_f32 f32, as_f64 f64
+            );
+            impl_cast!(@lossless $Type []);
+        }
+
+        impl<T, U> CheckedCast<$Type<U>> for $Type<T> where T: CheckedCast<U> {
+            #[inline]
+            fn checked_cast(self) -> Option<$Type<U>> {
+                Some($Type { $($get: self.$get.checked_cast()?),+ })
+            }
+        }
+
+        impl_cast!(@try $Type usize => u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type u8 => usize u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type u16 => usize u8 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type u32 => usize u8 u16 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type u64 => usize u8 u16 u32 u128 isize i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type u128 => usize u8 u16 u32 u64 isize i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type isize => usize u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type i8 => usize u8 u16 u32 u64 u128 isize i16 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type i16 => usize u8 u16 u32 u64 u128 isize i8 i32 i64 i128 f32 f64);
+        impl_cast!(@try $Type i32 => usize u8 u16 u32 u64 u128 isize i8 i16 i64 i128 f32 f64);
+        impl_cast!(@try $Type i64 => usize u8 u16 u32 u64 u128 isize i8 i16 i32 i128 f32 f64);
+        impl_cast!(@try $Type i128 => usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 f32 f64);
+        impl_cast!(@try $Type f32 => usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f64);
+        impl_cast!(@try $Type f64 => usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32);
+    };
+    (@as $Type:ident $($name:ident $to:ty),+) => {
+        $(
+            #[doc = concat!("Casts each element of `self` to `", stringify!($to), "` like the `as` operator.")]
+            #[inline]
+            #[must_use]
+            pub fn $name(self) -> $Type<$to> where T: AsPrimitive<$to> {
+                self.map(AsPrimitive::as_)
+            }
+        )+
+    };
+    // Same as above, for types with const generic parameters listed in brackets.
+    (@as $Type:ident $params:tt $($name:ident $to:ty),+) => {
+        $(impl_cast!(@as_one $Type $params $name $to);)+
+    };
+    (@as_one $Type:ident [$($param:ident),+] $name:ident $to:ty) => {
+        #[doc = concat!("Casts each element of `self` to `", stringify!($to), "` like the `as` operator.")]
+        #[inline]
+        #[must_use]
+        pub fn $name(self) -> $Type<$to, $($param),+> where T: AsPrimitive<$to> {
+            self.map(AsPrimitive::as_)
+        }
+    };
+    (@lossless $Type:ident [$($param:ident),*]) => {
+        /// Converts each element of `value` with [`From`], which the standard library only
+        /// implements for conversions that can't lose information, like `u8` to `u32` or `f32`
+        /// to `f64`.
+        ///
+        /// Use the `as_*` methods or `TryFrom` for lossy and fallible conversions.
+        #[inline]
+        #[must_use]
+        pub fn from_lossless<U>(value: $Type<U, $($param),*>) -> Self where T: From<U> {
+            value.map(T::from)
+        }
+    };
+    (@try $Type:ident $from:ty => $($to:ty)+) => {
+        $(
+            impl TryFrom<$Type<$from>> for $Type<$to> {
+                type Error = TryCastError;
+
+                #[inline]
+                fn try_from(value: $Type<$from>) -> Result<Self, Self::Error> {
+                    value.checked_cast().ok_or(TryCastError)
+                }
+            }
+        )+
+    };
+}
+
+/// Implements the reference variants of a single operation for `$Type`, a tuple struct around an
+/// array with the const generic parameters listed in brackets, in terms of the operation on the
+/// elements of that array.
+///
+/// Shared by [`SVector`](crate::svec::SVector) and [`SMatrix`](crate::smat::SMatrix), whose
+/// owned variants differ. The `@scalar` arm alone only implements the variants with a scalar
+/// right-hand side.
+macro_rules! impl_array_ref_op {
+    (impl $Op:ident for $Type:ident $params:tt { $op:ident }) => {
+        impl_array_ref_op!(@array impl $Op for $Type $params { $op });
+        impl_array_ref_op!(@scalar impl $Op for $Type $params { $op });
+    };
+    (@array impl $Op:ident for $Type:ident [$($param:ident),+] { $op:ident }) => {
+        impl<'a, T, $(const $param: usize),+> $Op<&'a $Type<T, $($param),+>> for $Type<T, $($param),+>
+        where
+            T: $Op<&'a T, Output = T>,
+        {
+            type Output = $Type<T, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: &'a $Type<T, $($param),+>) -> Self::Output {
+                $Type(crate::svec::zip_map(self.0, rhs.0.each_ref(), |a, b| a.$op(b)))
+            }
+        }
+        impl<'a, T, $(const $param: usize),+> $Op<$Type<T, $($param),+>> for &'a $Type<T, $($param),+>
+        where
+            &'a T: $Op<T, Output = T>,
+        {
+            type Output = $Type<T, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: $Type<T, $($param),+>) -> Self::Output {
+                $Type(crate::svec::zip_map(self.0.each_ref(), rhs.0, |a, b| a.$op(b)))
+            }
+        }
+        impl<'a, 'b, T, $(const $param: usize),+> $Op<&'a $Type<T, $($param),+>> for &'b $Type<T, $($param),+>
+        where
+            &'b T: $Op<&'a T, Output = T>,
+        {
+            type Output = $Type<T, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: &'a $Type<T, $($param),+>) -> Self::Output {
+                $Type(crate::svec::zip_map(self.0.each_ref(), rhs.0.each_ref(), |a, b| a.$op(b)))
+            }
+        }
+    };
+    (@scalar impl $Op:ident for $Type:ident [$($param:ident),+] { $op:ident }) => {
+        impl<'a, T, $(const $param: usize),+> $Op<T> for &'a $Type<T, $($param),+>
+        where
+            &'a T: $Op<T, Output = T>,
+            T: Copy,
+        {
+            type Output = $Type<T, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: T) -> Self::Output {
+                $Type(self.0.each_ref().map(|a| a.$op(rhs)))
+            }
+        }
+        impl<'a, 'b, T, $(const $param: usize),+> $Op<&'a T> for &'b $Type<T, $($param),+>
+        where
+            &'b T: $Op<&'a T, Output = T>,
+        {
+            type Output = $Type<T, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: &'a T) -> Self::Output {
+                $Type(self.0.each_ref().map(|a| a.$op(rhs)))
+            }
+        }
+    };
+}
+
+/// Implements the operations `$ops` with a primitive on the left-hand side for `$Type`, a tuple
+/// struct around an array with the const generic parameters listed in brackets.
+macro_rules! impl_array_prim {
+    ($Type:ident $params:tt, $($type:ident)+ => $ops:tt) => {
+        $(impl_array_prim!(@ops $Type $params $type $ops);)+
+    };
+    (@ops $Type:ident $params:tt $type:ident ($($Op:ident $op:ident),+)) => {
+        $(impl_array_prim!(@op $Type $params $type, $Op $op);)+
+    };
+    (@op $Type:ident [$($param:ident),+] $type:ident, $Op:ident $op:ident) => {
+        impl<$(const $param: usize),+> $Op<$Type<$type, $($param),+>> for $type {
+            type Output = $Type<$type, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: $Type<$type, $($param),+>) -> Self::Output {
+                $Type(rhs.0.map(|v| self.$op(v)))
+            }
+        }
+        impl<$(const $param: usize),+> $Op<&$Type<$type, $($param),+>> for $type {
+            type Output = $Type<$type, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: &$Type<$type, $($param),+>) -> Self::Output {
+                $Type(rhs.0.map(|v| self.$op(v)))
+            }
+        }
+        impl<$(const $param: usize),+> $Op<$Type<$type, $($param),+>> for &$type {
+            type Output = $Type<$type, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: $Type<$type, $($param),+>) -> Self::Output {
+                $Type(rhs.0.map(|v| self.$op(v)))
+            }
+        }
+        impl<$(const $param: usize),+> $Op<&$Type<$type, $($param),+>> for &$type {
+            type Output = $Type<$type, $($param),+>;
+
+            #[inline]
+            fn $op(self, rhs: &$Type<$type, $($param),+>) -> Self::Output {
+                $Type(rhs.0.map(|v| self.$op(v)))
+            }
+        }
+    };
+}
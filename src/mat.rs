@@ -1,9 +1,18 @@
+use crate::kernel::{MatKernel, TransformKernel};
 use crate::num::prelude::*;
 use crate::vec::*;
+use core::array;
+use core::mem::{align_of, offset_of, size_of};
 use core::ops::*;
+use core::slice;
 
 macro_rules! impl_op {
     (impl $Op:ident for $Mat:ident $Vec:ident { $op:ident } ($($get:tt),+)) => {
+        impl_op!(@scalar impl $Op for $Mat $Vec { $op } ($($get),+));
+        impl_op!(@vec impl $Op for $Mat $Vec { $op } ($($get),+));
+        impl_op!(@mat impl $Op for $Mat $Vec { $op } ($($get),+));
+    };
+    (@scalar impl $Op:ident for $Mat:ident $Vec:ident { $op:ident } ($($get:tt),+)) => {
         impl<T> $Op<T> for $Mat<T>
             where T: $Op<Output = T> + Copy
         {
@@ -14,7 +23,8 @@ macro_rules! impl_op {
                 }
             }
         }
-
+    };
+    (@vec impl $Op:ident for $Mat:ident $Vec:ident { $op:ident } ($($get:tt),+)) => {
         impl<T> $Op<$Vec<T>> for $Mat<T>
             where T: $Op<Output = T> + Copy
         {
@@ -25,8 +35,8 @@ macro_rules! impl_op {
                 }
             }
         }
-
-
+    };
+    (@mat impl $Op:ident for $Mat:ident $Vec:ident { $op:ident } ($($get:tt),+)) => {
         impl<T> $Op<$Mat<T>> for $Mat<T>
             where T: $Op<Output = T> + Copy
         {
@@ -42,24 +52,61 @@ macro_rules! impl_op {
 
 macro_rules! impl_assign_op {
     (impl $Op:ident for $Mat:ident $Vec:ident { $op:ident } ($($get:tt),+)) => {
-        // NOTE: Reminder that scalars T: Copy also implement Into<$Vec<T>>.
-        impl<V, T> $Op<V> for $Mat<T> where V: Into<$Mat<T>>, T: $Op<T> {
+        // NOTE: Unlike the vectors these don't accept `Into<$Mat<T>>`, the conversions between
+        // matrices of different sizes would silently apply to the right-hand side.
+        impl_assign_op!(@scalar impl $Op for $Mat $Vec { $op } ($($get),+));
+        impl<T> $Op<$Vec<T>> for $Mat<T> where T: $Op<T> + Copy {
+            #[inline]
+            fn $op(&mut self, rhs: $Vec<T>) {
+                $(self.$get.$op(rhs);)+
+            }
+        }
+        impl<T> $Op<$Mat<T>> for $Mat<T> where T: $Op<T> {
             #[inline]
-            fn $op(&mut self, rhs: V) {
-                let rhs = rhs.into();
+            fn $op(&mut self, rhs: $Mat<T>) {
                 $(self.$get.$op(rhs.$get);)+
             }
         }
     };
+    (@scalar impl $Op:ident for $Mat:ident $Vec:ident { $op:ident } ($($get:tt),+)) => {
+        impl<T> $Op<T> for $Mat<T> where T: $Op<T> + Copy {
+            #[inline]
+            fn $op(&mut self, rhs: T) {
+                $(self.$get.$op(rhs);)+
+            }
+        }
+    };
 }
 
+/// Implements a matrix with the columns `$get` of type `$Col`, `$Row` being the vector type of
+/// its rows and `$Transpose` the matrix type with rows and columns swapped.
 macro_rules! impl_mat {
-    ($Mat:ident $Vec:ident ($($get:tt),+)) => {
+    ($Mat:ident $Col:ident $Row:ident $Transpose:ident $gets:tt $indices:tt $row_gets:tt $row_indices:tt) => {
+        impl_mat!(@impl $Mat $Col $Row $Transpose $gets $gets $indices $row_gets $row_indices);
+    };
+    (@impl $Mat:ident $Col:ident $Row:ident $Transpose:ident ($($get:tt),+) $gets:tt ($($index:tt),+) ($($row_get:tt),+) ($($row_index:tt),+)) => {
+        /// A matrix stored in column-major order.
+        ///
+        /// Each field is a column, so `m.y.x` is the element in row 0 and column 1, which is also
+        /// `m[(0, 1)]`. Use [`Self::from_rows`] or [`Self::from_rows_array`] to build a matrix from
+        /// row-major data.
+        // NOTE: `#[repr(C)]` guarantees the columns are laid out contiguously in column-major
+        // order, which `as_slice` and the `bytemuck` impls rely on.
         #[derive(Debug, Default, Clone, Hash, PartialEq)]
+        #[repr(C)]
         pub struct $Mat<T> {
-            $(pub $get: $Vec<T>),+
+            $(pub $get: $Col<T>),+
         }
 
+        const _: () = {
+            assert!(size_of::<$Mat<u8>>() == $Row::<u8>::ELEM_COUNT * size_of::<$Col<u8>>());
+            assert!(size_of::<$Mat<f32>>() == $Row::<f32>::ELEM_COUNT * size_of::<$Col<f32>>());
+            assert!(size_of::<$Mat<f64>>() == $Row::<f64>::ELEM_COUNT * size_of::<$Col<f64>>());
+            assert!(align_of::<$Mat<f32>>() == align_of::<f32>());
+            assert!(align_of::<$Mat<f64>>() == align_of::<f64>());
+            $(assert!(offset_of!($Mat<f32>, $get) == $index * size_of::<$Col<f32>>());)+
+        };
+
         impl<T: Copy> Copy for $Mat<T> {}
 
         impl<T: Eq> Eq for $Mat<T> {}
@@ -70,40 +117,165 @@ macro_rules! impl_mat {
         unsafe impl<T: bytemuck::Pod> bytemuck::Pod for $Mat<T> {}
 
         impl<T: Zero> $Mat<T> {
-            pub const ZERO: Self = Self { $($get: $Vec::ZERO),+ };
+            pub const ZERO: Self = Self { $($get: $Col::ZERO),+ };
         }
         impl<T: One> $Mat<T> {
-            pub const ONE: Self = Self { $($get: $Vec::ONE),+ };
+            pub const ONE: Self = Self { $($get: $Col::ONE),+ };
         }
         impl<T: NegOne> $Mat<T> {
-            pub const NEG_ONE: Self = Self { $($get: $Vec::NEG_ONE),+ };
-        }
-        impl<T: Zero + One + Copy> $Mat<T> {
-            pub const IDENTITY: Self = Self::from_diagonal($Vec::ONE);
+            pub const NEG_ONE: Self = Self { $($get: $Col::NEG_ONE),+ };
         }
 
-        impl_op!(impl Add for $Mat $Vec { add } ($($get),+));
-        impl_op!(impl Sub for $Mat $Vec { sub } ($($get),+));
-        impl_op!(impl Mul for $Mat $Vec { mul } ($($get),+));
-        impl_op!(impl Div for $Mat $Vec { div } ($($get),+));
-        impl_op!(impl Rem for $Mat $Vec { rem } ($($get),+));
-        impl_assign_op!(impl AddAssign for $Mat $Vec { add_assign } ($($get),+));
-        impl_assign_op!(impl SubAssign for $Mat $Vec { sub_assign } ($($get),+));
-        impl_assign_op!(impl MulAssign for $Mat $Vec { mul_assign } ($($get),+));
-        impl_assign_op!(impl DivAssign for $Mat $Vec { div_assign } ($($get),+));
-        impl_assign_op!(impl RemAssign for $Mat $Vec { rem_assign } ($($get),+));
+        impl_cast!($Mat ($($get),+));
+
+        impl_op!(impl Add for $Mat $Col { add } ($($get),+));
+        impl_op!(impl Sub for $Mat $Col { sub } ($($get),+));
+        // NOTE: Matrix-matrix and matrix-vector multiplication are linear algebra products, not element-wise.
+        impl_op!(@scalar impl Mul for $Mat $Col { mul } ($($get),+));
+        impl_op!(impl Div for $Mat $Col { div } ($($get),+));
+        impl_op!(impl Rem for $Mat $Col { rem } ($($get),+));
+        impl_assign_op!(impl AddAssign for $Mat $Col { add_assign } ($($get),+));
+        impl_assign_op!(impl SubAssign for $Mat $Col { sub_assign } ($($get),+));
+        impl_assign_op!(@scalar impl MulAssign for $Mat $Col { mul_assign } ($($get),+));
+        impl_assign_op!(impl DivAssign for $Mat $Col { div_assign } ($($get),+));
+        impl_assign_op!(impl RemAssign for $Mat $Col { rem_assign } ($($get),+));
 
         impl<T> $Mat<T> {
-            pub const fn from_cols($($get: $Vec<T>),+) -> Self {
+            pub const fn from_cols($($get: $Col<T>),+) -> Self {
                 Self {
                     $($get),+
                 }
             }
 
-            pub const fn from_diagonal(diagonal: $Vec<T>) -> Self where T: Zero + Copy{
-                let mut out = Self::ZERO;
-                $(out.$get.$get = diagonal.$get;)+
-                out
+            /// Creates a matrix from its rows.
+            pub fn from_rows($($row_get: $Row<T>),+) -> Self where T: Copy {
+                $Transpose::from_cols($($row_get),+).transpose()
+            }
+
+            /// Creates a matrix from an array of columns.
+            pub fn from_cols_array_2d(m: &[[T; $Col::<()>::ELEM_COUNT]; $Row::<()>::ELEM_COUNT]) -> Self
+                where T: Copy
+            {
+                let [$($get),+] = *m;
+                Self { $($get: $get.into()),+ }
+            }
+            /// Creates a matrix from an array of its elements in column-major order.
+            pub fn from_cols_array(m: &[T; $Col::<()>::ELEM_COUNT * $Row::<()>::ELEM_COUNT]) -> Self
+                where T: Copy
+            {
+                Self::from_cols_array_2d(&array::from_fn(|col| {
+                    array::from_fn(|row| m[col * $Col::<()>::ELEM_COUNT + row])
+                }))
+            }
+            /// Creates a matrix from an array of its elements in row-major order, the layout
+            /// used by most file formats and by written notation.
+            pub fn from_rows_array(m: &[T; $Col::<()>::ELEM_COUNT * $Row::<()>::ELEM_COUNT]) -> Self
+                where T: Copy
+            {
+                $Transpose::from_cols_array(m).transpose()
+            }
+
+            /// Returns the columns of `self` as an array of arrays.
+            pub fn to_cols_array_2d(&self) -> [[T; $Col::<()>::ELEM_COUNT]; $Row::<()>::ELEM_COUNT]
+                where T: Copy
+            {
+                [$(self.$get.into_array()),+]
+            }
+            /// Returns the elements of `self` in column-major order.
+            pub fn to_cols_array(&self) -> [T; $Col::<()>::ELEM_COUNT * $Row::<()>::ELEM_COUNT]
+                where T: Copy
+            {
+                array::from_fn(|i| self.as_slice()[i])
+            }
+
+            /// View this matrix as an immutable slice of its elements in column-major order.
+            #[inline]
+            pub fn as_slice(&self) -> &[T] {
+                // SAFETY: `$Mat<T>` is `#[repr(C)]` and only contains `#[repr(C)]` columns of
+                // `T`, so its elements are contiguous.
+                unsafe {
+                    slice::from_raw_parts(
+                        self as *const Self as *const T,
+                        $Col::<T>::ELEM_COUNT * $Row::<T>::ELEM_COUNT,
+                    )
+                }
+            }
+            /// View this matrix as a mutable slice of its elements in column-major order.
+            #[inline]
+            pub fn as_mut_slice(&mut self) -> &mut [T] {
+                // SAFETY: See `as_slice`.
+                unsafe {
+                    slice::from_raw_parts_mut(
+                        self as *mut Self as *mut T,
+                        $Col::<T>::ELEM_COUNT * $Row::<T>::ELEM_COUNT,
+                    )
+                }
+            }
+
+            /// Returns an iterator over the elements of `self` in column-major order.
+            #[inline]
+            pub fn iter(&self) -> slice::Iter<'_, T> {
+                self.as_slice().iter()
+            }
+            /// Returns an iterator over mutable references to the elements of `self` in
+            /// column-major order.
+            #[inline]
+            pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+                self.as_mut_slice().iter_mut()
+            }
+
+            /// Returns the column at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub fn col(&self, index: usize) -> $Col<T> where T: Copy {
+                *self.col_ref(index)
+            }
+            /// Returns a mutable reference to the column at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub fn col_mut(&mut self, index: usize) -> &mut $Col<T> {
+                match index {
+                    $($index => &mut self.$get),+,
+                    _ => panic!("index out of bounds"),
+                }
+            }
+            #[inline]
+            fn col_ref(&self, index: usize) -> &$Col<T> {
+                match index {
+                    $($index => &self.$get),+,
+                    _ => panic!("index out of bounds"),
+                }
+            }
+
+            /// Returns the row at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub fn row(&self, index: usize) -> $Row<T> where T: Copy {
+                match index {
+                    $($row_index => impl_mat!(@row self $Row $gets $row_get)),+,
+                    _ => panic!("index out of bounds"),
+                }
+            }
+            /// Replaces the row at `index` with `row`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub fn set_row(&mut self, index: usize, row: $Row<T>) {
+                match index {
+                    $($row_index => { impl_mat!(@set_row self row $gets $row_get); })+
+                    _ => panic!("index out of bounds"),
+                }
             }
 
             pub fn map<D,F>(self, mut f: F) -> $Mat<D> where F: FnMut(T) -> D {
@@ -115,13 +287,252 @@ macro_rules! impl_mat {
             pub fn apply<F>(&mut self, f: F) where T: Copy, F: FnMut(T) -> T {
                 *self = self.map(f);
             }
+
+            /// Returns the transpose of `self`.
+            #[inline]
+            #[must_use]
+            pub fn transpose(&self) -> $Transpose<T> where T: Copy {
+                $Transpose::from_cols($(impl_mat!(@row self $Row $gets $row_get)),+)
+            }
+
+            /// Transforms the column vector `rhs` by `self`.
+            ///
+            /// The result is the linear combination of the columns of `self` weighted by the
+            /// elements of `rhs`.
+            #[inline]
+            #[must_use]
+            pub fn mul_vec(&self, rhs: $Row<T>) -> $Col<T>
+                where T: Add<Output = T> + Mul<Output = T> + Copy
+            {
+                reduce_op!(+, $(self.$get * rhs.$get),+)
+            }
+
+            /// Multiplies each element of `self` with the corresponding element of `rhs`.
+            ///
+            /// This is the Hadamard product, use the `*` operator for matrix multiplication.
+            #[inline]
+            #[must_use]
+            pub fn mul_elementwise(self, rhs: Self) -> Self where T: Mul<Output = T> + Copy {
+                Self {
+                    $($get: self.$get * rhs.$get),+
+                }
+            }
+        }
+
+        /// Indexes the element at `(row, column)`.
+        impl<T> Index<(usize, usize)> for $Mat<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+                &self.col_ref(col)[row]
+            }
+        }
+        impl<T> IndexMut<(usize, usize)> for $Mat<T> {
+            #[inline]
+            fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+                &mut self.col_mut(col)[row]
+            }
+        }
+
+        impl<T> Mul<$Row<T>> for $Mat<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            type Output = $Col<T>;
+            #[inline]
+            fn mul(self, rhs: $Row<T>) -> Self::Output {
+                self.mul_vec(rhs)
+            }
+        }
+        impl<T> Mul<$Mat<T>> for $Col<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            type Output = $Row<T>;
+            /// Transforms the row vector `self` by `rhs`, which is equivalent to multiplying
+            /// the transpose of `rhs` with `self`.
+            #[inline]
+            fn mul(self, rhs: $Mat<T>) -> Self::Output {
+                $Row::new($(self.dot(rhs.$get)),+)
+            }
+        }
+    };
+    // Builds row `$row_get` of the matrix `$m` as a vector.
+    (@row $m:ident $Row:ident ($($get:tt),+) $row_get:tt) => {
+        $Row::new($($m.$get.$row_get),+)
+    };
+    // Replaces row `$row_get` of the matrix `$m` with the vector `$v`.
+    (@set_row $m:ident $v:ident ($($get:tt),+) $row_get:tt) => {
+        $($m.$get.$row_get = $v.$get;)+
+    };
+    // The parts that only make sense for square matrices.
+    (@square $Mat:ident $Vec:ident ($($get:tt),+)) => {
+        impl<T: Zero + One + Copy> $Mat<T> {
+            pub const IDENTITY: Self = Self::from_diagonal($Vec::ONE);
+        }
+
+        impl<T> $Mat<T> {
+            pub const fn from_diagonal(diagonal: $Vec<T>) -> Self where T: Zero + Copy{
+                let mut out = Self::ZERO;
+                $(out.$get.$get = diagonal.$get;)+
+                out
+            }
+
+            /// Computes the matrix product of `self` and `rhs`.
+            ///
+            /// Matrices are stored in column-major order, so the result applies `rhs` first and
+            /// `self` second when transforming a column vector.
+            #[inline]
+            #[must_use]
+            pub fn mul_mat(&self, rhs: &Self) -> Self
+                where T: Add<Output = T> + Mul<Output = T> + Copy
+            {
+                <Self as MatKernel<T>>::mul_mat(self, rhs)
+            }
+        }
+
+        impl<T> Mul<$Mat<T>> for $Mat<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            type Output = $Mat<T>;
+            #[inline]
+            fn mul(self, rhs: $Mat<T>) -> Self::Output {
+                self.mul_mat(&rhs)
+            }
+        }
+        impl<T> $Mat<T>
+            where T: Float + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T> + Copy
+        {
+            /// Returns the inverse of `self`.
+            ///
+            /// If the matrix is not invertible the result will contain non-finite values, use
+            /// [`Self::try_inverse`] to check for this.
+            #[inline]
+            #[must_use]
+            pub fn inverse(&self) -> Self {
+                <Self as MatKernel<T>>::inverse(self)
+            }
+
+            /// Returns the inverse of `self`, or `None` if the matrix is singular or nearly so.
+            ///
+            /// A matrix counts as nearly singular when the absolute value of its determinant is
+            /// at most `T::EPSILON` times the product of the lengths of its columns. That product
+            /// is the largest determinant columns of those lengths can have, so the check does not
+            /// depend on the scale of the matrix.
+            ///
+            /// For `Simd` elements this is `None` as soon as a single lane is nearly singular.
+            #[inline]
+            #[must_use]
+            pub fn try_inverse(&self) -> Option<Self>
+                where T: Compare + Epsilon + Infinity
+            {
+                let adjugate = self.adjugate();
+                let det = $Vec::new($(self.$get.x),+).dot(adjugate.x);
+                let scale = reduce_op!(*, $(self.$get.length()),+);
+                let inv_det = T::ONE / det;
+                // NOTE: Both comparisons are false for NaN.
+                if T::all(det.abs().cmpgt(T::EPSILON * scale)) && T::all(inv_det.abs().cmplt(T::INFINITY)) {
+                    Some(adjugate * inv_det)
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<T> MatKernel<T> for $Mat<T> {
+            specializable! {
+                #[inline]
+                fn mul_mat(&self, rhs: &Self) -> Self
+                    where T: Add<Output = T> + Mul<Output = T> + Copy
+                {
+                    Self {
+                        $($get: self.mul_vec(rhs.$get)),+
+                    }
+                }
+            }
+            specializable! {
+                #[inline]
+                fn inverse(&self) -> Self
+                    where T: Float + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T> + Copy
+                {
+                    let adjugate = self.adjugate();
+                    let det = $Vec::new($(self.$get.x),+).dot(adjugate.x);
+                    adjugate * (T::ONE / det)
+                }
+            }
+        }
+
+        impl<T> MulAssign<$Mat<T>> for $Mat<T>
+            where T: Add<Output = T> + Mul<Output = T> + Copy
+        {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $Mat<T>) {
+                *self = self.mul_mat(&rhs);
+            }
         }
     };
 }
 
-impl_mat!(Mat2 Vec2 (x, y));
-impl_mat!(Mat3 Vec3 (x, y, z));
-impl_mat!(Mat4 Vec4 (x, y, z, w));
+/// Implements the product `$Lhs * $Rhs = $Out` for matrices of different shapes, `$get` being
+/// the columns of `$Rhs`.
+macro_rules! impl_mat_mul {
+    ($($Lhs:ident * $Rhs:ident = $Out:ident ($($get:tt),+);)+) => {
+        $(
+            impl<T> Mul<$Rhs<T>> for $Lhs<T>
+                where T: Add<Output = T> + Mul<Output = T> + Copy
+            {
+                type Output = $Out<T>;
+                #[inline]
+                fn mul(self, rhs: $Rhs<T>) -> Self::Output {
+                    $Out::from_cols($(self.mul_vec(rhs.$get)),+)
+                }
+            }
+        )+
+    };
+}
+
+// NOTE: `MatCxR` has `C` columns and `R` rows, like `matCxR` in GLSL and WGSL.
+impl_mat!(Mat2 Vec2 Vec2 Mat2 (x, y) (0, 1) (x, y) (0, 1));
+impl_mat!(Mat2x3 Vec3 Vec2 Mat3x2 (x, y) (0, 1) (x, y, z) (0, 1, 2));
+impl_mat!(Mat2x4 Vec4 Vec2 Mat4x2 (x, y) (0, 1) (x, y, z, w) (0, 1, 2, 3));
+impl_mat!(Mat3x2 Vec2 Vec3 Mat2x3 (x, y, z) (0, 1, 2) (x, y) (0, 1));
+impl_mat!(Mat3 Vec3 Vec3 Mat3 (x, y, z) (0, 1, 2) (x, y, z) (0, 1, 2));
+impl_mat!(Mat3x4 Vec4 Vec3 Mat4x3 (x, y, z) (0, 1, 2) (x, y, z, w) (0, 1, 2, 3));
+impl_mat!(Mat4x2 Vec2 Vec4 Mat2x4 (x, y, z, w) (0, 1, 2, 3) (x, y) (0, 1));
+impl_mat!(Mat4x3 Vec3 Vec4 Mat3x4 (x, y, z, w) (0, 1, 2, 3) (x, y, z) (0, 1, 2));
+impl_mat!(Mat4 Vec4 Vec4 Mat4 (x, y, z, w) (0, 1, 2, 3) (x, y, z, w) (0, 1, 2, 3));
+
+impl_mat!(@square Mat2 Vec2 (x, y));
+impl_mat!(@square Mat3 Vec3 (x, y, z));
+impl_mat!(@square Mat4 Vec4 (x, y, z, w));
+
+impl_mat_mul! {
+    Mat2 * Mat3x2 = Mat3x2 (x, y, z);
+    Mat2 * Mat4x2 = Mat4x2 (x, y, z, w);
+    Mat2x3 * Mat2 = Mat2x3 (x, y);
+    Mat2x3 * Mat3x2 = Mat3 (x, y, z);
+    Mat2x3 * Mat4x2 = Mat4x3 (x, y, z, w);
+    Mat2x4 * Mat2 = Mat2x4 (x, y);
+    Mat2x4 * Mat3x2 = Mat3x4 (x, y, z);
+    Mat2x4 * Mat4x2 = Mat4 (x, y, z, w);
+
+    Mat3x2 * Mat2x3 = Mat2 (x, y);
+    Mat3x2 * Mat3 = Mat3x2 (x, y, z);
+    Mat3x2 * Mat4x3 = Mat4x2 (x, y, z, w);
+    Mat3 * Mat2x3 = Mat2x3 (x, y);
+    Mat3 * Mat4x3 = Mat4x3 (x, y, z, w);
+    Mat3x4 * Mat2x3 = Mat2x4 (x, y);
+    Mat3x4 * Mat3 = Mat3x4 (x, y, z);
+    Mat3x4 * Mat4x3 = Mat4 (x, y, z, w);
+
+    Mat4x2 * Mat2x4 = Mat2 (x, y);
+    Mat4x2 * Mat3x4 = Mat3x2 (x, y, z);
+    Mat4x2 * Mat4 = Mat4x2 (x, y, z, w);
+    Mat4x3 * Mat2x4 = Mat2x3 (x, y);
+    Mat4x3 * Mat3x4 = Mat3 (x, y, z);
+    Mat4x3 * Mat4 = Mat4x3 (x, y, z, w);
+    Mat4 * Mat2x4 = Mat2x4 (x, y);
+    Mat4 * Mat3x4 = Mat3x4 (x, y, z);
+}
 
 impl<T> Mat2<T> {
     pub const fn new(m00: T, m01: T, m10: T, m11: T) -> Self {
@@ -132,6 +543,7 @@ impl<T> Mat2<T> {
     }
 }
 impl<T> Mat3<T> {
+    #[allow(clippy::too_many_arguments)]
     pub const fn new(
         m00: T,
         m01: T,
@@ -151,6 +563,7 @@ impl<T> Mat3<T> {
     }
 }
 impl<T> Mat4<T> {
+    #[allow(clippy::too_many_arguments)]
     pub const fn new(
         m00: T,
         m01: T,
@@ -177,3 +590,694 @@ impl<T> Mat4<T> {
         }
     }
 }
+impl<T> Mat2<T>
+where
+    T: Sub<Output = T> + Mul<Output = T> + Copy,
+{
+    /// Returns the determinant of `self`.
+    #[inline]
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        self.x.x * self.y.y - self.y.x * self.x.y
+    }
+}
+impl<T: Neg<Output = T> + Copy> Mat2<T> {
+    /// Returns the adjugate of `self`, the transpose of its cofactor matrix.
+    #[inline]
+    #[must_use]
+    pub fn adjugate(&self) -> Self {
+        Self::new(self.y.y, -self.x.y, -self.y.x, self.x.x)
+    }
+}
+
+impl<T> Mat3<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy,
+{
+    /// Returns the determinant of `self`.
+    #[inline]
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        self.z.dot(self.x.cross(self.y))
+    }
+
+    /// Returns the adjugate of `self`, the transpose of its cofactor matrix.
+    #[inline]
+    #[must_use]
+    pub fn adjugate(&self) -> Self {
+        Self::from_cols(
+            self.y.cross(self.z),
+            self.z.cross(self.x),
+            self.x.cross(self.y),
+        )
+        .transpose()
+    }
+}
+
+impl<T> Mat3<T>
+where
+    T: Float
+        + Compare<Mask = bool>
+        + Zero
+        + One
+        + Epsilon
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Computes the eigen decomposition of the symmetric matrix `self`.
+    ///
+    /// Returns the eigenvalues in ascending order and a rotation matrix whose columns are the
+    /// matching unit eigenvectors, so that `self == v * Mat3::from_diagonal(values) * v.transpose()`.
+    /// Repeated eigenvalues get an arbitrary orthonormal basis of their eigenspace.
+    ///
+    /// This uses the cyclic Jacobi method, which stays accurate for nearly repeated and zero
+    /// eigenvalues. Only symmetric matrices are supported, the result is unspecified otherwise.
+    ///
+    /// The sweeps branch on the elements, so this is only available for scalars and not for
+    /// `Simd` lanes.
+    #[must_use]
+    pub fn symmetric_eigen(&self) -> (Vec3<T>, Self) {
+        const MAX_SWEEPS: usize = 32;
+
+        let two = T::ONE + T::ONE;
+        let mut a = *self;
+        let mut v = Self::IDENTITY;
+        let norm = a.iter().fold(T::ZERO, |acc, &e| acc + e * e);
+        for _ in 0..MAX_SWEEPS {
+            let off = a[(0, 1)] * a[(0, 1)] + a[(0, 2)] * a[(0, 2)] + a[(1, 2)] * a[(1, 2)];
+            if off.cmple(T::EPSILON * T::EPSILON * norm) {
+                break;
+            }
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                let apq = a[(p, q)];
+                if apq.cmpeq(T::ZERO) {
+                    continue;
+                }
+                // NOTE: `t` is the tangent of the rotation angle that zeroes `a[(p, q)]`, taking
+                // the smaller root keeps the rotation below 45 degrees for stability.
+                let theta = (a[(q, q)] - a[(p, p)]) / (two * apq);
+                let t = T::ONE / (theta.abs() + theta.hypot(T::ONE));
+                let t = T::select(theta.cmplt(T::ZERO), -t, t);
+                let c = T::ONE / t.hypot(T::ONE);
+                let s = t * c;
+
+                for k in 0..3 {
+                    let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                    a[(k, p)] = c * akp - s * akq;
+                    a[(k, q)] = s * akp + c * akq;
+                }
+                for k in 0..3 {
+                    let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                    a[(p, k)] = c * apk - s * aqk;
+                    a[(q, k)] = s * apk + c * aqk;
+                }
+                a[(p, q)] = T::ZERO;
+                a[(q, p)] = T::ZERO;
+
+                for k in 0..3 {
+                    let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                    v[(k, p)] = c * vkp - s * vkq;
+                    v[(k, q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+
+        let mut values = Vec3::new(a.x.x, a.y.y, a.z.z);
+        for (i, j) in [(0, 1), (1, 2), (0, 1)] {
+            if values[j].cmplt(values[i]) {
+                values.as_mut_slice().swap(i, j);
+                let (ci, cj) = (v.col(i), v.col(j));
+                *v.col_mut(i) = cj;
+                *v.col_mut(j) = ci;
+            }
+        }
+        // NOTE: Jacobi rotations keep `v` a rotation, but the swaps above may have turned it into
+        // a reflection.
+        if v.determinant().cmplt(T::ZERO) {
+            v.z = -v.z;
+        }
+        (values, v)
+    }
+}
+
+impl<T> Mat4<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy,
+{
+    /// Returns the determinant of `self`.
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        let (m00, m01, m02, m03) = self.x.into_tuple();
+        let (m10, m11, m12, m13) = self.y.into_tuple();
+        let (m20, m21, m22, m23) = self.z.into_tuple();
+        let (m30, m31, m32, m33) = self.w.into_tuple();
+
+        let a2323 = m22 * m33 - m23 * m32;
+        let a1323 = m21 * m33 - m23 * m31;
+        let a1223 = m21 * m32 - m22 * m31;
+        let a0323 = m20 * m33 - m23 * m30;
+        let a0223 = m20 * m32 - m22 * m30;
+        let a0123 = m20 * m31 - m21 * m30;
+
+        m00 * (m11 * a2323 - m12 * a1323 + m13 * a1223)
+            - m01 * (m10 * a2323 - m12 * a0323 + m13 * a0223)
+            + m02 * (m10 * a1323 - m11 * a0323 + m13 * a0123)
+            - m03 * (m10 * a1223 - m11 * a0223 + m12 * a0123)
+    }
+}
+impl<T> Mat4<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Neg<Output = T> + Copy,
+{
+    /// Returns the adjugate of `self`, the transpose of its cofactor matrix.
+    #[must_use]
+    pub fn adjugate(&self) -> Self {
+        let (m00, m01, m02, m03) = self.x.into_tuple();
+        let (m10, m11, m12, m13) = self.y.into_tuple();
+        let (m20, m21, m22, m23) = self.z.into_tuple();
+        let (m30, m31, m32, m33) = self.w.into_tuple();
+
+        let coef00 = m22 * m33 - m32 * m23;
+        let coef02 = m12 * m33 - m32 * m13;
+        let coef03 = m12 * m23 - m22 * m13;
+
+        let coef04 = m21 * m33 - m31 * m23;
+        let coef06 = m11 * m33 - m31 * m13;
+        let coef07 = m11 * m23 - m21 * m13;
+
+        let coef08 = m21 * m32 - m31 * m22;
+        let coef10 = m11 * m32 - m31 * m12;
+        let coef11 = m11 * m22 - m21 * m12;
+
+        let coef12 = m20 * m33 - m30 * m23;
+        let coef14 = m10 * m33 - m30 * m13;
+        let coef15 = m10 * m23 - m20 * m13;
+
+        let coef16 = m20 * m32 - m30 * m22;
+        let coef18 = m10 * m32 - m30 * m12;
+        let coef19 = m10 * m22 - m20 * m12;
+
+        let coef20 = m20 * m31 - m30 * m21;
+        let coef22 = m10 * m31 - m30 * m11;
+        let coef23 = m10 * m21 - m20 * m11;
+
+        let fac0 = Vec4::new(coef00, coef00, coef02, coef03);
+        let fac1 = Vec4::new(coef04, coef04, coef06, coef07);
+        let fac2 = Vec4::new(coef08, coef08, coef10, coef11);
+        let fac3 = Vec4::new(coef12, coef12, coef14, coef15);
+        let fac4 = Vec4::new(coef16, coef16, coef18, coef19);
+        let fac5 = Vec4::new(coef20, coef20, coef22, coef23);
+
+        let vec0 = Vec4::new(m10, m00, m00, m00);
+        let vec1 = Vec4::new(m11, m01, m01, m01);
+        let vec2 = Vec4::new(m12, m02, m02, m02);
+        let vec3 = Vec4::new(m13, m03, m03, m03);
+
+        let inv0 = vec1 * fac0 - vec2 * fac1 + vec3 * fac2;
+        let inv1 = vec0 * fac0 - vec2 * fac3 + vec3 * fac4;
+        let inv2 = vec0 * fac1 - vec1 * fac3 + vec3 * fac5;
+        let inv3 = vec0 * fac2 - vec1 * fac4 + vec2 * fac5;
+
+        Self::from_cols(
+            Vec4::new(inv0.x, -inv0.y, inv0.z, -inv0.w),
+            Vec4::new(-inv1.x, inv1.y, -inv1.z, inv1.w),
+            Vec4::new(inv2.x, -inv2.y, inv2.z, -inv2.w),
+            Vec4::new(-inv3.x, inv3.y, -inv3.z, inv3.w),
+        )
+    }
+}
+impl<T> Mat4<T>
+where
+    T: Add<Output = T> + Mul<Output = T> + Copy,
+{
+    /// Transforms the given 3D point, treating it as having an implicit `w` of 1.
+    ///
+    /// The last row of `self` is ignored, so this is only correct for affine transforms. No
+    /// perspective divide is applied.
+    #[inline]
+    #[must_use]
+    pub fn transform_point3(&self, rhs: Vec3<T>) -> Vec3<T> {
+        (self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w).truncate()
+    }
+
+    /// Transforms the given 3D vector, treating it as having an implicit `w` of 0.
+    ///
+    /// This means the translation of `self` is not applied.
+    #[inline]
+    #[must_use]
+    pub fn transform_vector3(&self, rhs: Vec3<T>) -> Vec3<T> {
+        (self.x * rhs.x + self.y * rhs.y + self.z * rhs.z).truncate()
+    }
+
+    /// Transforms every point in `input` like [`Self::transform_point3`] and writes the results
+    /// to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `out` have different lengths.
+    pub fn transform_points(&self, input: &[Vec3<T>], out: &mut [Vec3<T>]) {
+        out.copy_from_slice(input);
+        self.transform_points_in_place(out);
+    }
+    /// Transforms every vector in `input` like [`Self::transform_vector3`] and writes the
+    /// results to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `out` have different lengths.
+    pub fn transform_vectors(&self, input: &[Vec3<T>], out: &mut [Vec3<T>]) {
+        out.copy_from_slice(input);
+        self.transform_vectors_in_place(out);
+    }
+
+    /// Transforms every point in `points` like [`Self::transform_point3`].
+    pub fn transform_points_in_place(&self, points: &mut [Vec3<T>]) {
+        <Self as TransformKernel<T>>::transform_points(self, points);
+    }
+    /// Transforms every vector in `vectors` like [`Self::transform_vector3`].
+    pub fn transform_vectors_in_place(&self, vectors: &mut [Vec3<T>]) {
+        <Self as TransformKernel<T>>::transform_vectors(self, vectors);
+    }
+}
+impl<T> TransformKernel<T> for Mat4<T> {
+    specializable! {
+        fn transform_points(&self, points: &mut [Vec3<T>])
+        where
+            T: Add<Output = T> + Mul<Output = T> + Copy,
+        {
+            for point in points {
+                *point = self.transform_point3(*point);
+            }
+        }
+    }
+    specializable! {
+        fn transform_vectors(&self, vectors: &mut [Vec3<T>])
+        where
+            T: Add<Output = T> + Mul<Output = T> + Copy,
+        {
+            for vector in vectors {
+                *vector = self.transform_vector3(*vector);
+            }
+        }
+    }
+}
+
+/// Embeds `value` in the upper-left block of an identity matrix.
+impl<T: Zero + One> From<Mat2<T>> for Mat3<T> {
+    #[inline]
+    fn from(value: Mat2<T>) -> Self {
+        Self::from_cols(value.x.extend(T::ZERO), value.y.extend(T::ZERO), Vec3::Z)
+    }
+}
+/// Embeds `value` in the upper-left block of an identity matrix.
+impl<T: Zero + One> From<Mat3<T>> for Mat4<T> {
+    #[inline]
+    fn from(value: Mat3<T>) -> Self {
+        Self::from_cols(
+            value.x.extend(T::ZERO),
+            value.y.extend(T::ZERO),
+            value.z.extend(T::ZERO),
+            Vec4::W,
+        )
+    }
+}
+/// Takes the upper-left 2x2 block of `value`.
+impl<T> From<Mat3<T>> for Mat2<T> {
+    #[inline]
+    fn from(value: Mat3<T>) -> Self {
+        Self::from_cols(value.x.truncate(), value.y.truncate())
+    }
+}
+/// Takes the upper-left 3x3 block of `value`.
+impl<T> From<Mat4<T>> for Mat3<T> {
+    #[inline]
+    fn from(value: Mat4<T>) -> Self {
+        Self::from_cols(value.x.truncate(), value.y.truncate(), value.z.truncate())
+    }
+}
+
+#[cfg(test)]
+mod mat_test {
+    use super::*;
+
+    #[test]
+    fn mat4_transform_slices() {
+        let m = Mat4::from_cols(
+            Vec4::new(0, 1, 0, 0),
+            Vec4::new(-2, 0, 0, 0),
+            Vec4::new(0, 0, 3, 0),
+            Vec4::new(5, 6, 7, 1),
+        );
+        let input = [Vec3::new(1, 2, 3), Vec3::new(-4, 0, 2), Vec3::new(0, 0, 0)];
+        let mut points = [Vec3::ZERO; 3];
+        let mut vectors = [Vec3::ZERO; 3];
+        m.transform_points(&input, &mut points);
+        m.transform_vectors(&input, &mut vectors);
+        for i in 0..input.len() {
+            assert_eq!(points[i], m.transform_point3(input[i]));
+            assert_eq!(vectors[i], m.transform_vector3(input[i]));
+        }
+
+        let mut in_place = input;
+        m.transform_points_in_place(&mut in_place);
+        assert_eq!(in_place, points);
+    }
+
+    #[test]
+    fn mat2_mul_mat() {
+        let a = Mat2::new(1, 2, 3, 4);
+        let b = Mat2::new(5, 6, 7, 8);
+        assert_eq!(a * b, Mat2::new(23, 34, 31, 46));
+    }
+    #[test]
+    fn mat3_mul_identity() {
+        let a = Mat3::new(1, 2, 3, 4, 5, 6, 7, 8, 9);
+        assert_eq!(a * Mat3::IDENTITY, a);
+        assert_eq!(Mat3::<i32>::IDENTITY * a, a);
+    }
+    #[test]
+    fn mat4_mul_mat() {
+        let a = Mat4::new(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+        let b = Mat4::new(
+            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+        );
+        let expected = Mat4::new(
+            538, 612, 686, 760, 650, 740, 830, 920, 762, 868, 974, 1080, 874, 996, 1118, 1240,
+        );
+        assert_eq!(a * b, expected);
+
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, expected);
+    }
+    #[test]
+    fn mat2_mul_vec() {
+        let a = Mat2::new(1, 2, 3, 4);
+        assert_eq!(a * Vec2::new(5, 6), Vec2::new(23, 34));
+        assert_eq!(Vec2::new(5, 6) * a, Vec2::new(17, 39));
+    }
+    #[test]
+    fn mat3_mul_vec() {
+        let a = Mat3::new(1, 2, 3, 4, 5, 6, 7, 8, 9);
+        assert_eq!(a.mul_vec(Vec3::X), a.x);
+        assert_eq!(a * Vec3::new(1, 1, 1), Vec3::new(12, 15, 18));
+    }
+    #[test]
+    fn mat4_transform_point3() {
+        let mut translate = Mat4::<i32>::IDENTITY;
+        translate.w = Vec4::new(10, 20, 30, 1);
+        let p = Vec3::new(1, 2, 3);
+        assert_eq!(translate.transform_point3(p), Vec3::new(11, 22, 33));
+        assert_eq!(translate.transform_vector3(p), p);
+        assert_eq!(translate * Vec4::new(1, 2, 3, 1), Vec4::new(11, 22, 33, 1));
+    }
+    #[test]
+    fn mat_transpose() {
+        assert_eq!(Mat2::new(1, 2, 3, 4).transpose(), Mat2::new(1, 3, 2, 4));
+        let a = Mat4::new(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(a.transpose().x, Vec4::new(1, 5, 9, 13));
+    }
+    #[test]
+    fn mat_determinant() {
+        assert_eq!(Mat2::new(1, 2, 3, 4).determinant(), -2);
+        assert_eq!(Mat3::new(2, 0, 0, 0, 3, 0, 0, 0, 4).determinant(), 24);
+        assert_eq!(Mat3::new(1, 2, 3, 4, 5, 6, 7, 8, 9).determinant(), 0);
+        let a = Mat4::new(1, 0, 2, 0, 0, 3, 0, 1, 4, 0, 5, 0, 0, 1, 0, 6);
+        assert_eq!(a.determinant(), -51);
+        assert_eq!(a.transpose().determinant(), -51);
+    }
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat2_inverse() {
+        let a = Mat2::new(3.0, 1.0, 5.0, 2.0);
+        assert_eq!(a.inverse(), Mat2::new(2.0, -1.0, -5.0, 3.0));
+        assert_eq!(a * a.inverse(), Mat2::IDENTITY);
+    }
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat3_inverse() {
+        let a = Mat3::new(2.0, 0.0, 0.0, 0.0, 4.0, 0.0, 1.0, 2.0, 1.0);
+        let inv = a.try_inverse().unwrap();
+        assert_eq!(
+            inv,
+            Mat3::new(0.5, 0.0, 0.0, 0.0, 0.25, 0.0, -0.5, -0.5, 1.0)
+        );
+        assert_eq!(a * inv, Mat3::IDENTITY);
+    }
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat4_inverse() {
+        let a = Mat4::new(
+            2.0, 0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.0, 8.0, 0.0, 1.0, 2.0, 3.0, 1.0,
+        );
+        let inv = a.inverse();
+        assert_eq!(
+            inv,
+            Mat4::new(
+                0.5, 0.0, 0.0, 0.0, 0.0, 0.25, 0.0, 0.0, 0.0, 0.0, 0.125, 0.0, -0.5, -0.5, -0.375,
+                1.0
+            )
+        );
+        assert_eq!(a * inv, Mat4::IDENTITY);
+        assert_eq!(inv * a, Mat4::IDENTITY);
+    }
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat_try_inverse_singular() {
+        assert_eq!(Mat2::new(1.0, 2.0, 2.0, 4.0).try_inverse(), None);
+        assert_eq!(Mat3::<f32>::ZERO.try_inverse(), None);
+        assert_eq!(
+            Mat4::new(
+                1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0
+            )
+            .try_inverse(),
+            None
+        );
+        assert_eq!(Mat2::new(1e-30_f32, 0.0, 0.0, 1e-30).try_inverse(), None);
+        assert_eq!(Mat2::new(1.0, 2.0, 2.0, 4.0 + 1e-15).try_inverse(), None);
+        // NOTE: The tolerance is relative, so small but well conditioned matrices are fine.
+        let small = Mat3::from_diagonal(Vec3::splat(0.0009765625_f32));
+        assert_eq!(
+            small.try_inverse(),
+            Some(Mat3::from_diagonal(Vec3::splat(1024.0)))
+        );
+    }
+    #[test]
+    fn mat2_mul_elementwise() {
+        let a = Mat2::new(1, 2, 3, 4);
+        let b = Mat2::new(5, 6, 7, 8);
+        assert_eq!(a.mul_elementwise(b), Mat2::new(5, 12, 21, 32));
+    }
+
+    #[test]
+    fn mat_assign_ops() {
+        let mut m = Mat2::new(1, 2, 3, 4);
+        m += Mat2::new(1, 1, 1, 1);
+        assert_eq!(m, Mat2::new(2, 3, 4, 5));
+        m -= 1;
+        assert_eq!(m, Mat2::new(1, 2, 3, 4));
+        m %= Vec2::new(2, 3);
+        assert_eq!(m, Mat2::new(1, 2, 1, 1));
+
+        let mut m = Mat3x2::from_cols(
+            Vec2::new(1.0, 2.0),
+            Vec2::new(3.0, 4.0),
+            Vec2::new(5.0, 6.0),
+        );
+        let expected = m * 2.0;
+        m *= 2.0;
+        assert_eq!(m, expected);
+    }
+
+    #[test]
+    fn mat_block_conversions() {
+        let m3 = Mat3::new(1, 2, 3, 4, 5, 6, 7, 8, 9);
+        let m4 = Mat4::from(m3);
+        assert_eq!(
+            m4,
+            Mat4::new(1, 2, 3, 0, 4, 5, 6, 0, 7, 8, 9, 0, 0, 0, 0, 1)
+        );
+        assert_eq!(Mat3::from(m4), m3);
+
+        let m2 = Mat2::from(m3);
+        assert_eq!(m2, Mat2::new(1, 2, 4, 5));
+        assert_eq!(Mat3::from(m2), Mat3::new(1, 2, 0, 4, 5, 0, 0, 0, 1));
+    }
+
+    #[test]
+    fn mat_casts() {
+        let m = Mat2::new(1.5_f32, -2.0, 3.0, 4.0);
+        assert_eq!(m.as_f64(), Mat2::new(1.5, -2.0, 3.0, 4.0));
+        assert_eq!(m.as_i32(), Mat2::new(1, -2, 3, 4));
+        assert_eq!(Mat2::<i32>::try_from(m), Ok(Mat2::new(1, -2, 3, 4)));
+        assert_eq!(Mat2::<u32>::try_from(m), Err(TryCastError));
+    }
+
+    #[test]
+    fn mat_rectangular() {
+        let a = Mat2x3::from_cols(Vec3::new(1, 2, 3), Vec3::new(4, 5, 6));
+        let b = Mat3x2::from_cols(Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(1, 1));
+        assert_eq!(
+            a.transpose(),
+            Mat3x2::from_cols(Vec2::new(1, 4), Vec2::new(2, 5), Vec2::new(3, 6))
+        );
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(a * Vec2::new(1, -1), Vec3::new(-3, -3, -3));
+        assert_eq!(Vec3::new(1, 0, 1) * a, Vec2::new(4, 10));
+        assert_eq!(a * b, Mat3::new(1, 2, 3, 4, 5, 6, 5, 7, 9));
+        assert_eq!(b * a, Mat2::new(4, 5, 10, 11));
+        assert_eq!(Mat3::IDENTITY * a, a);
+        assert_eq!(a * Mat2::IDENTITY, a);
+    }
+
+    #[test]
+    fn mat_rectangular_matches_smatrix() {
+        use crate::smat::SMatrix;
+
+        let a = Mat3x4::from_cols(
+            Vec4::new(1, 2, 3, 4),
+            Vec4::new(5, 6, 7, 8),
+            Vec4::new(9, 10, 11, 12),
+        );
+        let b = Mat4x3::from_cols(
+            Vec3::new(1, -1, 2),
+            Vec3::new(0, 3, 1),
+            Vec3::new(-2, 1, 1),
+            Vec3::new(4, 0, -1),
+        );
+        let (sa, sb) = (SMatrix::from(a), SMatrix::from(b));
+        assert_eq!(SMatrix::from(a * b), sa * sb);
+        assert_eq!(SMatrix::from(b * a), sb * sa);
+        assert_eq!(SMatrix::from(a.transpose()), sa.transpose());
+        assert_eq!(
+            Mat2x4::from(SMatrix::from(a * Mat2x3::from_cols(Vec3::X, Vec3::Z))),
+            Mat2x4::from_cols(a.x, a.z)
+        );
+    }
+
+    #[test]
+    fn mat_rows_cols_and_indexing() {
+        let mut m = Mat2x3::from_cols(Vec3::new(1, 2, 3), Vec3::new(4, 5, 6));
+        assert_eq!(m[(0, 1)], 4);
+        assert_eq!(m[(2, 0)], 3);
+        assert_eq!(m.col(1), Vec3::new(4, 5, 6));
+        assert_eq!(m.row(2), Vec2::new(3, 6));
+        m[(1, 1)] = 9;
+        assert_eq!(m.y.y, 9);
+        m.set_row(0, Vec2::new(7, 8));
+        assert_eq!(m.x, Vec3::new(7, 2, 3));
+        assert_eq!(m.y, Vec3::new(8, 9, 6));
+        m.col_mut(0).z = 0;
+        assert_eq!(m.as_slice(), &[7, 2, 0, 8, 9, 6]);
+        assert_eq!(m.iter().sum::<i32>(), 32);
+        m.iter_mut().for_each(|v| *v *= 2);
+        assert_eq!(m.to_cols_array(), [14, 4, 0, 16, 18, 12]);
+    }
+
+    #[test]
+    fn mat_array_conversions() {
+        let m = Mat3::new(1, 2, 3, 4, 5, 6, 7, 8, 9);
+        assert_eq!(m.to_cols_array(), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(m.to_cols_array_2d(), [[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(Mat3::from_cols_array(&m.to_cols_array()), m);
+        assert_eq!(Mat3::from_cols_array_2d(&m.to_cols_array_2d()), m);
+        assert_eq!(Mat3::from_rows_array(&[1, 4, 7, 2, 5, 8, 3, 6, 9]), m);
+        assert_eq!(
+            Mat3::from_rows(Vec3::new(1, 4, 7), Vec3::new(2, 5, 8), Vec3::new(3, 6, 9)),
+            m
+        );
+
+        // A 3x4 row-major affine transform as found in many file formats.
+        let rows = [1, 0, 0, 5, 0, 1, 0, 6, 0, 0, 1, 7];
+        let a = Mat4x3::from_rows_array(&rows);
+        assert_eq!(a.w, Vec3::new(5, 6, 7));
+        assert_eq!(a.row(0), Vec4::new(1, 0, 0, 5));
+        assert_eq!(a.transpose().to_cols_array(), rows);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mat_row_out_of_bounds() {
+        Mat3x2::<i32>::ZERO.row(2);
+    }
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn assert_eigen(m: Mat3<f64>, expected: [f64; 3]) {
+        let (values, v) = m.symmetric_eigen();
+        for i in 0..3 {
+            assert!(
+                (values[i] - expected[i]).abs() < 1e-9,
+                "{values:?} != {expected:?}"
+            );
+            let col = v.col(i);
+            assert!((m * col - col * values[i]).length() < 1e-9);
+        }
+        assert!((v.determinant() - 1.0).abs() < 1e-9);
+        let vtv = v.transpose() * v;
+        for (a, b) in vtv.iter().zip(Mat3::<f64>::IDENTITY.iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        let rebuilt = v * Mat3::from_diagonal(values) * v.transpose();
+        for (a, b) in rebuilt.iter().zip(m.iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat3_symmetric_eigen() {
+        assert_eigen(
+            Mat3::new(2.0, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 5.0),
+            [1.0, 3.0, 5.0],
+        );
+        assert_eigen(
+            Mat3::new(4.0, 1.0, -2.0, 1.0, 2.0, 0.0, -2.0, 0.0, 3.0),
+            // Roots of the characteristic polynomial `(x - 1)(x^2 - 8x + 13)`.
+            [1.0, 4.0 - 3.0_f64.sqrt(), 4.0 + 3.0_f64.sqrt()],
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat3_symmetric_eigen_degenerate() {
+        // Zero, identity and already diagonal matrices.
+        assert_eigen(Mat3::ZERO, [0.0; 3]);
+        assert_eigen(Mat3::IDENTITY, [1.0; 3]);
+        assert_eigen(
+            Mat3::from_diagonal(Vec3::new(3.0, -1.0, 2.0)),
+            [-1.0, 2.0, 3.0],
+        );
+        // A repeated eigenvalue in a rotated basis.
+        let r = Mat3::from_cols(
+            Vec3::new(2.0, 2.0, 1.0) / 3.0,
+            Vec3::new(-2.0, 1.0, 2.0) / 3.0,
+            Vec3::new(1.0, -2.0, 2.0) / 3.0,
+        );
+        assert_eigen(
+            r * Mat3::from_diagonal(Vec3::new(2.0, 2.0, 5.0)) * r.transpose(),
+            [2.0, 2.0, 5.0],
+        );
+        // The covariance of collinear points has rank one.
+        let d = Vec3::new(1.0, 2.0, -2.0);
+        assert_eigen(Mat3::from_cols(d * d.x, d * d.y, d * d.z), [0.0, 0.0, 9.0]);
+        // Eigenvalues that only differ by rounding.
+        assert_eigen(
+            Mat3::new(1.0, 1e-12, 0.0, 1e-12, 1.0, 0.0, 0.0, 0.0, 1.0),
+            [1.0 - 1e-12, 1.0, 1.0 + 1e-12],
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn mat3_symmetric_eigen_f32() {
+        let m = Mat3::new(2.0_f32, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 5.0);
+        let (values, v) = m.symmetric_eigen();
+        assert!((values - Vec3::new(1.0, 3.0, 5.0)).length() < 1e-5);
+        assert!((m * v.x - v.x * values.x).length() < 1e-5);
+    }
+}
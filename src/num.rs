@@ -1,16 +1,47 @@
 pub(crate) mod prelude {
+    pub use super::Compare;
     pub use super::Float;
+    pub use super::Integer;
     pub use super::consts::*;
+    pub use super::{AsPrimitive, CheckedCast, TryCastError};
 }
 
 pub trait Float: PartialEq + PartialOrd {
     fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
 
     fn floor(self) -> Self;
     fn ceil(self) -> Self;
     fn round(self) -> Self;
     fn trunc(self) -> Self;
     fn fract(self) -> Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn recip(self) -> Self;
+    fn mul_add(self, a: Self, b: Self) -> Self;
+
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sin_cos(self) -> (Self, Self)
+    where
+        Self: Sized;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+
+    #[allow(clippy::wrong_self_convention)]
+    fn is_nan(self) -> bool;
+    #[allow(clippy::wrong_self_convention)]
+    fn is_finite(self) -> bool;
 }
 
 #[macro_export]
@@ -20,6 +51,8 @@ macro_rules! derive_float {
             impl Float for $type {
                 #[inline(always)]
                 fn sqrt(self) -> Self { self.sqrt() }
+                #[inline(always)]
+                fn hypot(self, other: Self) -> Self { self.hypot(other) }
 
                 #[inline(always)]
                 fn floor(self) -> Self { self.floor() }
@@ -31,14 +64,302 @@ macro_rules! derive_float {
                 fn trunc(self) -> Self { self.trunc() }
                 #[inline(always)]
                 fn fract(self) -> Self { self.fract() }
+
+                #[inline(always)]
+                fn abs(self) -> Self { self.abs() }
+                #[inline(always)]
+                fn signum(self) -> Self { self.signum() }
+                #[inline(always)]
+                fn copysign(self, sign: Self) -> Self { self.copysign(sign) }
+                #[inline(always)]
+                fn recip(self) -> Self { self.recip() }
+                #[inline(always)]
+                fn mul_add(self, a: Self, b: Self) -> Self { self.mul_add(a, b) }
+
+                #[inline(always)]
+                fn sin(self) -> Self { self.sin() }
+                #[inline(always)]
+                fn cos(self) -> Self { self.cos() }
+                #[inline(always)]
+                fn tan(self) -> Self { self.tan() }
+                #[inline(always)]
+                fn sin_cos(self) -> (Self, Self) { self.sin_cos() }
+                #[inline(always)]
+                fn asin(self) -> Self { self.asin() }
+                #[inline(always)]
+                fn acos(self) -> Self { self.acos() }
+                #[inline(always)]
+                fn atan(self) -> Self { self.atan() }
+                #[inline(always)]
+                fn atan2(self, other: Self) -> Self { self.atan2(other) }
+
+                #[inline(always)]
+                fn exp(self) -> Self { self.exp() }
+                #[inline(always)]
+                fn ln(self) -> Self { self.ln() }
+                #[inline(always)]
+                fn powf(self, n: Self) -> Self { self.powf(n) }
+                #[inline(always)]
+                fn powi(self, n: i32) -> Self { self.powi(n) }
+
+                #[inline(always)]
+                fn is_nan(self) -> bool { self.is_nan() }
+                #[inline(always)]
+                fn is_finite(self) -> bool { self.is_finite() }
             }
         )+
     };
 }
 
-#[cfg(not(feature = "num-traits"))]
+#[cfg(all(feature = "std", not(feature = "num-traits")))]
 derive_float!(f32 f64);
 
+/// Comparisons that return a mask instead of a single `bool`.
+///
+/// For primitives the mask is a `bool`. For `core::simd::Simd` every lane is compared on its own,
+/// unlike [`PartialOrd`] which compares the lanes lexicographically.
+///
+/// With the `num-traits` feature this is implemented for every `num_traits::Num + PartialOrd`
+/// type. Other scalars, like fixed-point or wrapper types, get an implementation on top of their
+/// [`PartialOrd`] implementation with [`derive_compare!`](crate::derive_compare):
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// struct Fixed(i32);
+///
+/// owl::derive_compare!(Fixed);
+///
+/// let v = owl::vec::Vec2::new(Fixed(3), Fixed(-1));
+/// assert_eq!(v.min_element(), Fixed(-1));
+/// assert_eq!(v.max(owl::vec::Vec2::splat(Fixed(0))), owl::vec::Vec2::new(Fixed(3), Fixed(0)));
+/// ```
+pub trait Compare: Sized {
+    type Mask: Copy;
+
+    fn cmplt(self, rhs: Self) -> Self::Mask;
+    fn cmple(self, rhs: Self) -> Self::Mask;
+    fn cmpgt(self, rhs: Self) -> Self::Mask;
+    fn cmpge(self, rhs: Self) -> Self::Mask;
+    fn cmpeq(self, rhs: Self) -> Self::Mask;
+    fn cmpne(self, rhs: Self) -> Self::Mask;
+
+    /// Returns `if_true` where `mask` is set and `if_false` everywhere else.
+    fn select(mask: Self::Mask, if_true: Self, if_false: Self) -> Self;
+
+    /// Returns whether every lane of `mask` is set.
+    fn all(mask: Self::Mask) -> bool;
+    /// Returns whether any lane of `mask` is set.
+    fn any(mask: Self::Mask) -> bool;
+}
+
+/// Implements [`Compare`] with a `bool` mask for types that implement [`PartialOrd`] and [`Copy`].
+#[macro_export]
+macro_rules! derive_compare {
+    ($($type:ty)+) => {
+        $(
+            impl $crate::num::Compare for $type {
+                type Mask = bool;
+
+                #[inline(always)]
+                fn cmplt(self, rhs: Self) -> bool { self < rhs }
+                #[inline(always)]
+                fn cmple(self, rhs: Self) -> bool { self <= rhs }
+                #[inline(always)]
+                fn cmpgt(self, rhs: Self) -> bool { self > rhs }
+                #[inline(always)]
+                fn cmpge(self, rhs: Self) -> bool { self >= rhs }
+                #[inline(always)]
+                fn cmpeq(self, rhs: Self) -> bool { self == rhs }
+                #[inline(always)]
+                fn cmpne(self, rhs: Self) -> bool { self != rhs }
+
+                #[inline(always)]
+                fn select(mask: bool, if_true: Self, if_false: Self) -> Self {
+                    if mask { if_true } else { if_false }
+                }
+
+                #[inline(always)]
+                fn all(mask: bool) -> bool { mask }
+                #[inline(always)]
+                fn any(mask: bool) -> bool { mask }
+            }
+        )+
+    };
+}
+
+#[cfg(not(feature = "num-traits"))]
+derive_compare!(usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+
+/// Overflow-aware arithmetic for integers.
+pub trait Integer: Sized {
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+
+    fn div_euclid(self, rhs: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($type:ty)+) => {
+        $(
+            impl Integer for $type {
+                #[inline(always)]
+                fn wrapping_add(self, rhs: Self) -> Self { self.wrapping_add(rhs) }
+                #[inline(always)]
+                fn wrapping_sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+                #[inline(always)]
+                fn wrapping_mul(self, rhs: Self) -> Self { self.wrapping_mul(rhs) }
+
+                #[inline(always)]
+                fn saturating_add(self, rhs: Self) -> Self { self.saturating_add(rhs) }
+                #[inline(always)]
+                fn saturating_sub(self, rhs: Self) -> Self { self.saturating_sub(rhs) }
+                #[inline(always)]
+                fn saturating_mul(self, rhs: Self) -> Self { self.saturating_mul(rhs) }
+
+                #[inline(always)]
+                fn checked_add(self, rhs: Self) -> Option<Self> { self.checked_add(rhs) }
+                #[inline(always)]
+                fn checked_sub(self, rhs: Self) -> Option<Self> { self.checked_sub(rhs) }
+                #[inline(always)]
+                fn checked_mul(self, rhs: Self) -> Option<Self> { self.checked_mul(rhs) }
+                #[inline(always)]
+                fn checked_div(self, rhs: Self) -> Option<Self> { self.checked_div(rhs) }
+                #[inline(always)]
+                fn checked_rem(self, rhs: Self) -> Option<Self> { self.checked_rem(rhs) }
+
+                #[inline(always)]
+                fn div_euclid(self, rhs: Self) -> Self { self.div_euclid(rhs) }
+                #[inline(always)]
+                fn rem_euclid(self, rhs: Self) -> Self { self.rem_euclid(rhs) }
+            }
+        )+
+    };
+}
+
+impl_integer!(usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128);
+
+/// Conversion between primitives with the `as` operator.
+pub trait AsPrimitive<T> {
+    fn as_(self) -> T;
+}
+
+/// Conversion between primitives that fails instead of overflowing.
+///
+/// Conversions from floats to integers truncate towards zero and fail for NaN, infinities and
+/// values out of range. Conversions to floats round to the nearest representable value and only
+/// fail if a finite value would become infinite.
+pub trait CheckedCast<T> {
+    fn checked_cast(self) -> Option<T>;
+}
+
+/// The error returned by the checked `TryFrom` conversions between vectors or matrices of
+/// different element types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryCastError;
+
+impl core::fmt::Display for TryCastError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("element out of range for the target type")
+    }
+}
+
+impl core::error::Error for TryCastError {}
+
+macro_rules! impl_primitive_cast {
+    ($($from:ty)+ => $to:tt) => {
+        $(impl_primitive_cast!(@as $from => $to);)+
+    };
+    (@as $from:ty => [$($to:ty)+]) => {
+        $(
+            impl AsPrimitive<$to> for $from {
+                #[inline(always)]
+                fn as_(self) -> $to { self as $to }
+            }
+        )+
+    };
+    (@int $($from:ty)+ => int $to:tt) => {
+        $(impl_primitive_cast!(@int_int $from => $to);)+
+    };
+    (@int_int $from:ty => [$($to:ty)+]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> { <$to>::try_from(self).ok() }
+            }
+        )+
+    };
+    (@int $($from:ty)+ => float $to:tt) => {
+        $(impl_primitive_cast!(@int_float $from => $to);)+
+    };
+    (@int_float $from:ty => [$($to:ty)+]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    // NOTE: Only `u128` and `i128` can exceed the range of `f32`.
+                    let out = self as $to;
+                    if out.is_infinite() { None } else { Some(out) }
+                }
+            }
+        )+
+    };
+    (@float $($from:ty)+ => int $to:tt) => {
+        $(impl_primitive_cast!(@float_int $from => $to);)+
+    };
+    (@float_int $from:ty => [$($to:ty)+]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    // NOTE: `MIN` is a power of two so it is exact, `MAX + 1` is as well after
+                    // rounding. `MIN - 1` rounds to `MIN` for large types, hence both checks.
+                    let min = <$to>::MIN as $from;
+                    let max = <$to>::MAX as $from + 1.0;
+                    if (self >= min || self > min - 1.0) && self < max {
+                        Some(self as $to)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )+
+    };
+    (@float $($from:ty)+ => float $to:tt) => {
+        $(impl_primitive_cast!(@float_float $from => $to);)+
+    };
+    (@float_float $from:ty => [$($to:ty)+]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    let out = self as $to;
+                    if out.is_infinite() && self.is_finite() { None } else { Some(out) }
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive_cast!(usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64
+    => [usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64]);
+impl_primitive_cast!(@int usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128
+    => int [usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128]);
+impl_primitive_cast!(@int usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 => float [f32 f64]);
+impl_primitive_cast!(@float f32 f64 => int [usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128]);
+impl_primitive_cast!(@float f32 f64 => float [f32 f64]);
+
 pub mod consts {
     pub trait Zero {
         const ZERO: Self;
@@ -49,6 +370,18 @@ pub mod consts {
     pub trait NegOne {
         const NEG_ONE: Self;
     }
+    pub trait Epsilon {
+        const EPSILON: Self;
+    }
+    pub trait Pi {
+        const PI: Self;
+    }
+    pub trait Tau {
+        const TAU: Self;
+    }
+    pub trait Infinity {
+        const INFINITY: Self;
+    }
 
     macro_rules! impl_const {
         (impl $trait:ident $const:ident for $($type:ty: $val:expr)+) => {
@@ -73,4 +406,90 @@ pub mod consts {
     impl_const!(impl One ONE for usize:1 u8:1 u16:1 u32:1 u128:1 isize:1 i8:1 i16:1 i32:1 i64:1 i128:1 f32:1.0 f64:1.0);
 
     impl_const!(impl NegOne NEG_ONE for isize:-1 i8:-1 i16:-1 i32:-1 i64:-1 i128:-1 f32:-1.0 f64:-1.0);
+    impl_const!(impl Epsilon EPSILON for f32 f64);
+    impl_const!(impl Pi PI for f32: core::f32::consts::PI f64: core::f64::consts::PI);
+    impl_const!(impl Tau TAU for f32: core::f32::consts::TAU f64: core::f64::consts::TAU);
+    impl_const!(impl Infinity INFINITY for f32 f64);
+}
+
+#[cfg(test)]
+mod num_test {
+    use super::consts::*;
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn float_functions() {
+        assert_eq!(Float::powi(2.0_f32, 3), 8.0);
+        assert_eq!(Float::powf(4.0_f64, 0.5), 2.0);
+        assert_eq!(Float::hypot(3.0_f32, 4.0), 5.0);
+        assert_eq!(Float::recip(4.0_f32), 0.25);
+        assert_eq!(Float::mul_add(2.0_f64, 3.0, 1.0), 7.0);
+        assert_eq!(Float::signum(-0.5_f32), -1.0);
+        assert_eq!(Float::copysign(2.0_f32, -0.0), -2.0);
+        assert_eq!(Float::exp(0.0_f32), 1.0);
+        assert_eq!(Float::ln(1.0_f64), 0.0);
+        assert!((Float::exp(Float::ln(3.0_f64)) - 3.0).abs() < 1e-12);
+        assert!((Float::atan(Float::tan(0.5_f64)) - 0.5).abs() < 1e-12);
+        assert!((Float::asin(1.0_f32) - f32::PI / 2.0).abs() < 1e-6);
+        assert!(Float::is_nan(f32::NAN));
+        assert!(!Float::is_finite(f64::INFINITY));
+        assert!(Float::signum(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn float_consts() {
+        assert_eq!(<f32 as Tau>::TAU, 2.0 * <f32 as Pi>::PI);
+        assert_eq!(<f64 as Infinity>::INFINITY, f64::INFINITY);
+        assert_eq!(<f64 as Epsilon>::EPSILON, f64::EPSILON);
+    }
+
+    #[test]
+    #[cfg(feature = "num-traits")]
+    fn float_consts_match_num_traits() {
+        fn check<T>()
+        where
+            T: num_traits::Float + num_traits::FloatConst + Epsilon + Pi + Tau + Infinity,
+        {
+            assert!(T::EPSILON == T::epsilon());
+            assert!(<T as Pi>::PI == <T as num_traits::FloatConst>::PI());
+            assert!(<T as Tau>::TAU == <T as num_traits::FloatConst>::TAU());
+            assert!(T::INFINITY == T::infinity());
+        }
+        check::<f32>();
+        check::<f64>();
+    }
+
+    #[test]
+    fn primitive_checked_cast() {
+        assert_eq!(CheckedCast::<i8>::checked_cast(-128.5_f32), Some(-128));
+        assert_eq!(CheckedCast::<i8>::checked_cast(127.9_f32), Some(127));
+        assert_eq!(CheckedCast::<i8>::checked_cast(128.0_f32), None);
+        assert_eq!(CheckedCast::<u8>::checked_cast(-0.5_f64), Some(0));
+        assert_eq!(CheckedCast::<u8>::checked_cast(-1.0_f64), None);
+        assert_eq!(
+            CheckedCast::<i64>::checked_cast(i64::MIN as f64),
+            Some(i64::MIN)
+        );
+        assert_eq!(CheckedCast::<i64>::checked_cast(i64::MAX as f64), None);
+        assert_eq!(CheckedCast::<u32>::checked_cast(f32::NAN), None);
+        assert_eq!(CheckedCast::<u32>::checked_cast(f32::INFINITY), None);
+        assert_eq!(CheckedCast::<f32>::checked_cast(1e300_f64), None);
+        assert_eq!(
+            CheckedCast::<f32>::checked_cast(f64::INFINITY),
+            Some(f32::INFINITY)
+        );
+        assert_eq!(CheckedCast::<u8>::checked_cast(256_u32), None);
+        assert_eq!(CheckedCast::<f32>::checked_cast(u128::MAX), None);
+        assert_eq!(
+            CheckedCast::<f32>::checked_cast(i128::MIN),
+            Some(i128::MIN as f32)
+        );
+        assert_eq!(
+            CheckedCast::<f64>::checked_cast(u128::MAX),
+            Some(u128::MAX as f64)
+        );
+        assert_eq!(CheckedCast::<u8>::checked_cast(255_u32), Some(255));
+        assert_eq!(AsPrimitive::<u8>::as_(300_i32), 44);
+    }
 }
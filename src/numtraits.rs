@@ -1,12 +1,17 @@
 use crate::num::prelude::*;
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "libm"))]
 impl<T: num_traits::Float> Float for T {
     #[inline]
     fn sqrt(self) -> Self {
         self.sqrt()
     }
 
+    #[inline]
+    fn hypot(self, other: Self) -> Self {
+        self.hypot(other)
+    }
+
     #[inline]
     fn floor(self) -> Self {
         self.floor()
@@ -31,6 +36,101 @@ impl<T: num_traits::Float> Float for T {
     fn fract(self) -> Self {
         self.fract()
     }
+
+    #[inline]
+    fn abs(self) -> Self {
+        self.abs()
+    }
+
+    #[inline]
+    fn signum(self) -> Self {
+        self.signum()
+    }
+
+    #[inline]
+    fn copysign(self, sign: Self) -> Self {
+        self.copysign(sign)
+    }
+
+    #[inline]
+    fn recip(self) -> Self {
+        self.recip()
+    }
+
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self.mul_add(a, b)
+    }
+
+    #[inline]
+    fn sin(self) -> Self {
+        self.sin()
+    }
+
+    #[inline]
+    fn cos(self) -> Self {
+        self.cos()
+    }
+
+    #[inline]
+    fn tan(self) -> Self {
+        self.tan()
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        self.sin_cos()
+    }
+
+    #[inline]
+    fn asin(self) -> Self {
+        self.asin()
+    }
+
+    #[inline]
+    fn acos(self) -> Self {
+        self.acos()
+    }
+
+    #[inline]
+    fn atan(self) -> Self {
+        self.atan()
+    }
+
+    #[inline]
+    fn atan2(self, other: Self) -> Self {
+        self.atan2(other)
+    }
+
+    #[inline]
+    fn exp(self) -> Self {
+        self.exp()
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        self.ln()
+    }
+
+    #[inline]
+    fn powf(self, n: Self) -> Self {
+        self.powf(n)
+    }
+
+    #[inline]
+    fn powi(self, n: i32) -> Self {
+        self.powi(n)
+    }
+
+    #[inline]
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        self.is_finite()
+    }
 }
 
 impl<T: num_traits::identities::ConstZero> Zero for T {
@@ -39,3 +139,51 @@ impl<T: num_traits::identities::ConstZero> Zero for T {
 impl<T: num_traits::identities::ConstOne> One for T {
     const ONE: Self = T::ONE;
 }
+
+// NOTE: `Epsilon`, `Pi`, `Tau` and `Infinity` can't be bridged like `Zero` and `One`, num-traits
+// only exposes them as functions (`Float::epsilon`, `FloatConst::PI`, ...) that can't be called
+// in an associated `const`. `num::consts` implements them for `f32` and `f64` with or without
+// this feature, other `num_traits::Float` types have to implement them by hand.
+
+impl<T: num_traits::Num + PartialOrd + Copy> Compare for T {
+    type Mask = bool;
+
+    #[inline(always)]
+    fn cmplt(self, rhs: Self) -> bool {
+        self < rhs
+    }
+    #[inline(always)]
+    fn cmple(self, rhs: Self) -> bool {
+        self <= rhs
+    }
+    #[inline(always)]
+    fn cmpgt(self, rhs: Self) -> bool {
+        self > rhs
+    }
+    #[inline(always)]
+    fn cmpge(self, rhs: Self) -> bool {
+        self >= rhs
+    }
+    #[inline(always)]
+    fn cmpeq(self, rhs: Self) -> bool {
+        self == rhs
+    }
+    #[inline(always)]
+    fn cmpne(self, rhs: Self) -> bool {
+        self != rhs
+    }
+
+    #[inline(always)]
+    fn select(mask: bool, if_true: Self, if_false: Self) -> Self {
+        if mask { if_true } else { if_false }
+    }
+
+    #[inline(always)]
+    fn all(mask: bool) -> bool {
+        mask
+    }
+    #[inline(always)]
+    fn any(mask: bool) -> bool {
+        mask
+    }
+}
@@ -0,0 +1,403 @@
+use crate::mat::*;
+use crate::num::prelude::*;
+use crate::vec::*;
+use core::ops::*;
+
+/// The range that depth values are mapped to in normalized device coordinates.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DepthRange {
+    /// Depth is mapped to `[-1, 1]`, as used by OpenGL.
+    NegOneToOne,
+    /// Depth is mapped to `[0, 1]`, as used by Vulkan, Direct3D, Metal and WebGPU.
+    ZeroToOne,
+}
+
+impl<T> Mat4<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Returns the x and y scale of a perspective projection with a vertical field of view of
+    /// `fov_y` radians.
+    #[inline]
+    fn perspective_scale(fov_y: T, aspect_ratio: T) -> (T, T) {
+        let half = T::ONE / (T::ONE + T::ONE);
+        let (sin_fov, cos_fov) = (fov_y * half).sin_cos();
+        let h = cos_fov / sin_fov;
+        (h / aspect_ratio, h)
+    }
+
+    /// Creates a right-handed perspective projection matrix, which looks down the negative z
+    /// axis.
+    ///
+    /// `fov_y` is the vertical field of view in radians and `aspect_ratio` is width divided by
+    /// height. The near plane is mapped to the start of `depth` and the far plane to `1`.
+    #[must_use]
+    pub fn perspective_rh(
+        fov_y: T,
+        aspect_ratio: T,
+        z_near: T,
+        z_far: T,
+        depth: DepthRange,
+    ) -> Self {
+        let (w, h) = Self::perspective_scale(fov_y, aspect_ratio);
+        let inv_depth = T::ONE / (z_near - z_far);
+        let (a, b) = match depth {
+            DepthRange::NegOneToOne => (
+                (z_far + z_near) * inv_depth,
+                (T::ONE + T::ONE) * z_far * z_near * inv_depth,
+            ),
+            DepthRange::ZeroToOne => (z_far * inv_depth, z_far * z_near * inv_depth),
+        };
+        Self::from_cols(
+            Vec4::new(w, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, h, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, a, -T::ONE),
+            Vec4::new(T::ZERO, T::ZERO, b, T::ZERO),
+        )
+    }
+
+    /// Creates a left-handed perspective projection matrix, which looks down the positive z
+    /// axis.
+    ///
+    /// `fov_y` is the vertical field of view in radians and `aspect_ratio` is width divided by
+    /// height. The near plane is mapped to the start of `depth` and the far plane to `1`.
+    #[must_use]
+    pub fn perspective_lh(
+        fov_y: T,
+        aspect_ratio: T,
+        z_near: T,
+        z_far: T,
+        depth: DepthRange,
+    ) -> Self {
+        let (w, h) = Self::perspective_scale(fov_y, aspect_ratio);
+        let inv_depth = T::ONE / (z_far - z_near);
+        let (a, b) = match depth {
+            DepthRange::NegOneToOne => (
+                (z_far + z_near) * inv_depth,
+                -(T::ONE + T::ONE) * z_far * z_near * inv_depth,
+            ),
+            DepthRange::ZeroToOne => (z_far * inv_depth, -z_far * z_near * inv_depth),
+        };
+        Self::from_cols(
+            Vec4::new(w, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, h, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, a, T::ONE),
+            Vec4::new(T::ZERO, T::ZERO, b, T::ZERO),
+        )
+    }
+
+    /// Creates a right-handed perspective projection matrix with the far plane at infinity.
+    ///
+    /// The near plane is mapped to the start of `depth` and infinity to `1`.
+    #[must_use]
+    pub fn perspective_infinite_rh(
+        fov_y: T,
+        aspect_ratio: T,
+        z_near: T,
+        depth: DepthRange,
+    ) -> Self {
+        let (w, h) = Self::perspective_scale(fov_y, aspect_ratio);
+        let b = match depth {
+            DepthRange::NegOneToOne => -(T::ONE + T::ONE) * z_near,
+            DepthRange::ZeroToOne => -z_near,
+        };
+        Self::from_cols(
+            Vec4::new(w, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, h, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, -T::ONE, -T::ONE),
+            Vec4::new(T::ZERO, T::ZERO, b, T::ZERO),
+        )
+    }
+
+    /// Creates a left-handed perspective projection matrix with the far plane at infinity.
+    ///
+    /// The near plane is mapped to the start of `depth` and infinity to `1`.
+    #[must_use]
+    pub fn perspective_infinite_lh(
+        fov_y: T,
+        aspect_ratio: T,
+        z_near: T,
+        depth: DepthRange,
+    ) -> Self {
+        let (w, h) = Self::perspective_scale(fov_y, aspect_ratio);
+        let b = match depth {
+            DepthRange::NegOneToOne => -(T::ONE + T::ONE) * z_near,
+            DepthRange::ZeroToOne => -z_near,
+        };
+        Self::from_cols(
+            Vec4::new(w, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, h, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, T::ONE, T::ONE),
+            Vec4::new(T::ZERO, T::ZERO, b, T::ZERO),
+        )
+    }
+
+    /// Creates a right-handed perspective projection matrix with the far plane at infinity and
+    /// reversed depth.
+    ///
+    /// The near plane is mapped to `1` and infinity to the start of `depth`, which gives a
+    /// better distribution of floating point depth precision.
+    #[must_use]
+    pub fn perspective_infinite_reverse_rh(
+        fov_y: T,
+        aspect_ratio: T,
+        z_near: T,
+        depth: DepthRange,
+    ) -> Self {
+        let (w, h) = Self::perspective_scale(fov_y, aspect_ratio);
+        let (a, b) = match depth {
+            DepthRange::NegOneToOne => (T::ONE, (T::ONE + T::ONE) * z_near),
+            DepthRange::ZeroToOne => (T::ZERO, z_near),
+        };
+        Self::from_cols(
+            Vec4::new(w, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, h, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, a, -T::ONE),
+            Vec4::new(T::ZERO, T::ZERO, b, T::ZERO),
+        )
+    }
+
+    /// Creates a left-handed perspective projection matrix with the far plane at infinity and
+    /// reversed depth.
+    ///
+    /// The near plane is mapped to `1` and infinity to the start of `depth`, which gives a
+    /// better distribution of floating point depth precision.
+    #[must_use]
+    pub fn perspective_infinite_reverse_lh(
+        fov_y: T,
+        aspect_ratio: T,
+        z_near: T,
+        depth: DepthRange,
+    ) -> Self {
+        let (w, h) = Self::perspective_scale(fov_y, aspect_ratio);
+        let (a, b) = match depth {
+            DepthRange::NegOneToOne => (-T::ONE, (T::ONE + T::ONE) * z_near),
+            DepthRange::ZeroToOne => (T::ZERO, z_near),
+        };
+        Self::from_cols(
+            Vec4::new(w, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, h, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, a, T::ONE),
+            Vec4::new(T::ZERO, T::ZERO, b, T::ZERO),
+        )
+    }
+
+    /// Creates a right-handed orthographic projection matrix, which looks down the negative z
+    /// axis.
+    ///
+    /// The near plane is mapped to the start of `depth` and the far plane to `1`.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn orthographic_rh(
+        left: T,
+        right: T,
+        bottom: T,
+        top: T,
+        z_near: T,
+        z_far: T,
+        depth: DepthRange,
+    ) -> Self {
+        let inv_depth = T::ONE / (z_near - z_far);
+        let (a, b) = match depth {
+            DepthRange::NegOneToOne => {
+                ((T::ONE + T::ONE) * inv_depth, (z_far + z_near) * inv_depth)
+            }
+            DepthRange::ZeroToOne => (inv_depth, z_near * inv_depth),
+        };
+        Self::orthographic(left, right, bottom, top, a, b)
+    }
+
+    /// Creates a left-handed orthographic projection matrix, which looks down the positive z
+    /// axis.
+    ///
+    /// The near plane is mapped to the start of `depth` and the far plane to `1`.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn orthographic_lh(
+        left: T,
+        right: T,
+        bottom: T,
+        top: T,
+        z_near: T,
+        z_far: T,
+        depth: DepthRange,
+    ) -> Self {
+        let inv_depth = T::ONE / (z_far - z_near);
+        let (a, b) = match depth {
+            DepthRange::NegOneToOne => {
+                ((T::ONE + T::ONE) * inv_depth, -(z_far + z_near) * inv_depth)
+            }
+            DepthRange::ZeroToOne => (inv_depth, -z_near * inv_depth),
+        };
+        Self::orthographic(left, right, bottom, top, a, b)
+    }
+
+    /// Creates an orthographic projection matrix with the depth mapping `z * a + b`.
+    #[inline]
+    fn orthographic(left: T, right: T, bottom: T, top: T, a: T, b: T) -> Self {
+        let inv_width = T::ONE / (right - left);
+        let inv_height = T::ONE / (top - bottom);
+        let two = T::ONE + T::ONE;
+        Self::from_cols(
+            Vec4::new(two * inv_width, T::ZERO, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, two * inv_height, T::ZERO, T::ZERO),
+            Vec4::new(T::ZERO, T::ZERO, a, T::ZERO),
+            Vec4::new(
+                -(right + left) * inv_width,
+                -(top + bottom) * inv_height,
+                b,
+                T::ONE,
+            ),
+        )
+    }
+
+    /// Creates a right-handed view matrix for a camera at `eye` looking in the direction `dir`.
+    ///
+    /// The camera looks down its negative z axis and `up` is used to orient it.
+    #[must_use]
+    pub fn look_to_rh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self {
+        let f = dir.normalize();
+        let s = f.cross(up).normalize();
+        let u = s.cross(f);
+        Self::from_cols(
+            Vec4::new(s.x, u.x, -f.x, T::ZERO),
+            Vec4::new(s.y, u.y, -f.y, T::ZERO),
+            Vec4::new(s.z, u.z, -f.z, T::ZERO),
+            Vec4::new(-eye.dot(s), -eye.dot(u), eye.dot(f), T::ONE),
+        )
+    }
+
+    /// Creates a left-handed view matrix for a camera at `eye` looking in the direction `dir`.
+    ///
+    /// The camera looks down its positive z axis and `up` is used to orient it.
+    #[inline]
+    #[must_use]
+    pub fn look_to_lh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self {
+        Self::look_to_rh(eye, -dir, up)
+    }
+
+    /// Creates a right-handed view matrix for a camera at `eye` looking at `center`.
+    ///
+    /// The camera looks down its negative z axis and `up` is used to orient it.
+    #[inline]
+    #[must_use]
+    pub fn look_at_rh(eye: Vec3<T>, center: Vec3<T>, up: Vec3<T>) -> Self {
+        Self::look_to_rh(eye, center - eye, up)
+    }
+
+    /// Creates a left-handed view matrix for a camera at `eye` looking at `center`.
+    ///
+    /// The camera looks down its positive z axis and `up` is used to orient it.
+    #[inline]
+    #[must_use]
+    pub fn look_at_lh(eye: Vec3<T>, center: Vec3<T>, up: Vec3<T>) -> Self {
+        Self::look_to_lh(eye, center - eye, up)
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod projection_test {
+    use super::*;
+    use core::f64::consts::FRAC_PI_2;
+
+    fn assert_approx_eq(a: &[f64], b: &[f64]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9),
+            "{a:?} != {b:?}"
+        );
+    }
+    /// Projects the view space point `p` and returns its normalized device coordinates.
+    fn project(m: Mat4<f64>, p: Vec3<f64>) -> Vec3<f64> {
+        (m * p.to_homogeneous_point()).xyz_div_w()
+    }
+
+    #[test]
+    fn perspective_depth() {
+        for (depth, d0) in [
+            (DepthRange::NegOneToOne, -1.0),
+            (DepthRange::ZeroToOne, 0.0),
+        ] {
+            let rh = Mat4::perspective_rh(FRAC_PI_2, 2.0, 0.5, 100.0, depth);
+            assert_approx_eq(&project(rh, Vec3::new(0.0, 0.0, -0.5)), &[0.0, 0.0, d0]);
+            assert_approx_eq(
+                &project(rh, Vec3::new(200.0, 100.0, -100.0)),
+                &[1.0, 1.0, 1.0],
+            );
+
+            let lh = Mat4::perspective_lh(FRAC_PI_2, 2.0, 0.5, 100.0, depth);
+            assert_approx_eq(&project(lh, Vec3::new(0.0, 0.0, 0.5)), &[0.0, 0.0, d0]);
+            assert_approx_eq(
+                &project(lh, Vec3::new(-200.0, 100.0, 100.0)),
+                &[-1.0, 1.0, 1.0],
+            );
+        }
+    }
+
+    #[test]
+    fn perspective_infinite_depth() {
+        for (depth, d0) in [
+            (DepthRange::NegOneToOne, -1.0),
+            (DepthRange::ZeroToOne, 0.0),
+        ] {
+            let far = 1e12;
+            let rh = Mat4::perspective_infinite_rh(FRAC_PI_2, 1.0, 0.5, depth);
+            assert_approx_eq(&project(rh, Vec3::new(0.0, 0.0, -0.5)), &[0.0, 0.0, d0]);
+            assert_approx_eq(&project(rh, Vec3::new(0.0, 0.0, -far)), &[0.0, 0.0, 1.0]);
+
+            let lh = Mat4::perspective_infinite_lh(FRAC_PI_2, 1.0, 0.5, depth);
+            assert_approx_eq(&project(lh, Vec3::new(0.0, 0.0, 0.5)), &[0.0, 0.0, d0]);
+            assert_approx_eq(&project(lh, Vec3::new(0.0, 0.0, far)), &[0.0, 0.0, 1.0]);
+
+            let rh = Mat4::perspective_infinite_reverse_rh(FRAC_PI_2, 1.0, 0.5, depth);
+            assert_approx_eq(&project(rh, Vec3::new(0.0, 0.0, -0.5)), &[0.0, 0.0, 1.0]);
+            assert_approx_eq(&project(rh, Vec3::new(0.0, 0.0, -far)), &[0.0, 0.0, d0]);
+
+            let lh = Mat4::perspective_infinite_reverse_lh(FRAC_PI_2, 1.0, 0.5, depth);
+            assert_approx_eq(&project(lh, Vec3::new(0.0, 0.0, 0.5)), &[0.0, 0.0, 1.0]);
+            assert_approx_eq(&project(lh, Vec3::new(0.0, 0.0, far)), &[0.0, 0.0, d0]);
+        }
+    }
+
+    #[test]
+    fn orthographic_depth() {
+        for (depth, d0) in [
+            (DepthRange::NegOneToOne, -1.0),
+            (DepthRange::ZeroToOne, 0.0),
+        ] {
+            let rh = Mat4::orthographic_rh(-2.0, 4.0, -1.0, 3.0, 1.0, 11.0, depth);
+            assert_approx_eq(&project(rh, Vec3::new(-2.0, -1.0, -1.0)), &[-1.0, -1.0, d0]);
+            assert_approx_eq(&project(rh, Vec3::new(4.0, 3.0, -11.0)), &[1.0, 1.0, 1.0]);
+
+            let lh = Mat4::orthographic_lh(-2.0, 4.0, -1.0, 3.0, 1.0, 11.0, depth);
+            assert_approx_eq(&project(lh, Vec3::new(-2.0, -1.0, 1.0)), &[-1.0, -1.0, d0]);
+            assert_approx_eq(&project(lh, Vec3::new(4.0, 3.0, 11.0)), &[1.0, 1.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn look_at() {
+        let eye = Vec3::new(1.0, 2.0, 3.0);
+        let center = Vec3::new(1.0, 2.0, -7.0);
+
+        let rh = Mat4::look_at_rh(eye, center, Vec3::Y);
+        assert_approx_eq(&rh.transform_point3(eye), &Vec3::ZERO);
+        assert_approx_eq(&rh.transform_point3(center), &[0.0, 0.0, -10.0]);
+        assert_approx_eq(&rh.transform_point3(eye + Vec3::Y), &Vec3::Y);
+
+        let lh = Mat4::look_at_lh(eye, center, Vec3::Y);
+        assert_approx_eq(&lh.transform_point3(eye), &Vec3::ZERO);
+        assert_approx_eq(&lh.transform_point3(center), &[0.0, 0.0, 10.0]);
+        assert_approx_eq(&lh.transform_point3(eye + Vec3::Y), &Vec3::Y);
+
+        let to = Mat4::look_to_rh(eye, center - eye, Vec3::Y);
+        assert_eq!(to, rh);
+    }
+}
@@ -0,0 +1,536 @@
+use crate::mat::*;
+use crate::num::prelude::*;
+use crate::vec::*;
+use core::ops::*;
+
+/// A quaternion representing an orientation.
+///
+/// The quaternion is stored as a [`Vec4`] where `x`, `y` and `z` hold the vector part and `w`
+/// holds the scalar part. All rotation functions expect the quaternion to be normalized.
+#[derive(Debug, Default, Clone, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct Quat<T>(Vec4<T>);
+
+impl<T: Copy> Copy for Quat<T> {}
+
+impl<T: Eq> Eq for Quat<T> {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Quat<T> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Quat<T> {}
+
+impl<T: Zero + One> Quat<T> {
+    /// The identity quaternion, corresponding to no rotation.
+    pub const IDENTITY: Self = Self(Vec4::W);
+}
+
+impl<T> Quat<T> {
+    /// Creates a quaternion from its `x`, `y`, `z` and `w` components.
+    ///
+    /// This does not normalize the input.
+    #[inline]
+    pub const fn from_xyzw(x: T, y: T, z: T, w: T) -> Self {
+        Self(Vec4::new(x, y, z, w))
+    }
+
+    /// Creates a quaternion from a [`Vec4`] holding its `x`, `y`, `z` and `w` components.
+    ///
+    /// This does not normalize the input.
+    #[inline]
+    pub const fn from_vec4(v: Vec4<T>) -> Self {
+        Self(v)
+    }
+
+    /// Returns the underlying [`Vec4`] of `self`.
+    #[inline]
+    pub fn to_vec4(self) -> Vec4<T> {
+        self.0
+    }
+
+    /// Returns the vector part of `self`.
+    #[inline]
+    pub fn xyz(self) -> Vec3<T> {
+        let Vec4 { x, y, z, .. } = self.0;
+        Vec3::new(x, y, z)
+    }
+}
+
+impl<T: Neg<Output = T>> Quat<T> {
+    /// Returns the conjugate of `self`, which for a normalized quaternion is the inverse
+    /// rotation.
+    #[inline]
+    #[must_use]
+    pub fn conjugate(self) -> Self {
+        let Vec4 { x, y, z, w } = self.0;
+        Self::from_xyzw(-x, -y, -z, w)
+    }
+}
+
+impl<T> Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates a quaternion for a rotation of `angle` radians around `axis`.
+    ///
+    /// The axis must be normalized.
+    #[inline]
+    #[must_use]
+    pub fn from_axis_angle(axis: Vec3<T>, angle: T) -> Self {
+        let half = T::ONE / (T::ONE + T::ONE);
+        let (s, c) = (angle * half).sin_cos();
+        let v = axis * s;
+        Self::from_xyzw(v.x, v.y, v.z, c)
+    }
+
+    /// Creates a quaternion from a 3x3 rotation matrix.
+    ///
+    /// The matrix must be a pure rotation, i.e. orthonormal with a determinant of 1.
+    #[must_use]
+    pub fn from_mat3(mat: &Mat3<T>) -> Self {
+        let (m00, m01, m02) = mat.x.into_tuple();
+        let (m10, m11, m12) = mat.y.into_tuple();
+        let (m20, m21, m22) = mat.z.into_tuple();
+        let half = T::ONE / (T::ONE + T::ONE);
+        if m22 <= T::ZERO {
+            // x^2 + y^2 >= z^2 + w^2
+            let dif10 = m11 - m00;
+            let omm22 = T::ONE - m22;
+            if dif10 <= T::ZERO {
+                // x^2 >= y^2
+                let four_xsq = omm22 - dif10;
+                let inv4x = half / four_xsq.sqrt();
+                Self::from_xyzw(
+                    four_xsq * inv4x,
+                    (m01 + m10) * inv4x,
+                    (m02 + m20) * inv4x,
+                    (m12 - m21) * inv4x,
+                )
+            } else {
+                // y^2 >= x^2
+                let four_ysq = omm22 + dif10;
+                let inv4y = half / four_ysq.sqrt();
+                Self::from_xyzw(
+                    (m01 + m10) * inv4y,
+                    four_ysq * inv4y,
+                    (m12 + m21) * inv4y,
+                    (m20 - m02) * inv4y,
+                )
+            }
+        } else {
+            // z^2 + w^2 >= x^2 + y^2
+            let sum10 = m11 + m00;
+            let opm22 = T::ONE + m22;
+            if sum10 <= T::ZERO {
+                // z^2 >= w^2
+                let four_zsq = opm22 - sum10;
+                let inv4z = half / four_zsq.sqrt();
+                Self::from_xyzw(
+                    (m02 + m20) * inv4z,
+                    (m12 + m21) * inv4z,
+                    four_zsq * inv4z,
+                    (m01 - m10) * inv4z,
+                )
+            } else {
+                // w^2 >= z^2
+                let four_wsq = opm22 + sum10;
+                let inv4w = half / four_wsq.sqrt();
+                Self::from_xyzw(
+                    (m12 - m21) * inv4w,
+                    (m20 - m02) * inv4w,
+                    (m01 - m10) * inv4w,
+                    four_wsq * inv4w,
+                )
+            }
+        }
+    }
+
+    /// Creates a quaternion from the upper 3x3 rotation part of a 4x4 matrix.
+    #[inline]
+    #[must_use]
+    pub fn from_mat4(mat: &Mat4<T>) -> Self {
+        Self::from_mat3(&Mat3::from(*mat))
+    }
+
+    /// Converts `self` into a 3x3 rotation matrix.
+    #[must_use]
+    pub fn to_mat3(self) -> Mat3<T> {
+        let Vec4 { x, y, z, w } = self.0;
+        let x2 = x + x;
+        let y2 = y + y;
+        let z2 = z + z;
+        let xx = x * x2;
+        let xy = x * y2;
+        let xz = x * z2;
+        let yy = y * y2;
+        let yz = y * z2;
+        let zz = z * z2;
+        let wx = w * x2;
+        let wy = w * y2;
+        let wz = w * z2;
+
+        Mat3::from_cols(
+            Vec3::new(T::ONE - (yy + zz), xy + wz, xz - wy),
+            Vec3::new(xy - wz, T::ONE - (xx + zz), yz + wx),
+            Vec3::new(xz + wy, yz - wx, T::ONE - (xx + yy)),
+        )
+    }
+
+    /// Converts `self` into a 4x4 rotation matrix with no translation.
+    #[must_use]
+    pub fn to_mat4(self) -> Mat4<T> {
+        Mat4::from(self.to_mat3())
+    }
+
+    /// Computes the dot product of `self` and `rhs`.
+    #[inline]
+    pub fn dot(self, rhs: Self) -> T {
+        self.0.dot(rhs.0)
+    }
+
+    /// Returns the length of `self`.
+    #[inline]
+    pub fn length(self) -> T {
+        self.0.length()
+    }
+
+    /// Returns `self` normalized to length 1.
+    #[inline]
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        Self(self.0.normalize())
+    }
+
+    /// Returns the inverse of `self`.
+    ///
+    /// For a normalized quaternion this is equal to [`Self::conjugate`], which is cheaper to
+    /// compute.
+    #[inline]
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self(self.conjugate().0 / self.0.length_squared())
+    }
+
+    /// Computes the Hamilton product of `self` and `rhs`.
+    ///
+    /// The resulting rotation applies `rhs` first and `self` second.
+    #[inline]
+    #[must_use]
+    pub fn mul_quat(self, rhs: Self) -> Self {
+        let Vec4 {
+            x: x0,
+            y: y0,
+            z: z0,
+            w: w0,
+        } = self.0;
+        let Vec4 {
+            x: x1,
+            y: y1,
+            z: z1,
+            w: w1,
+        } = rhs.0;
+        Self::from_xyzw(
+            w0 * x1 + x0 * w1 + y0 * z1 - z0 * y1,
+            w0 * y1 - x0 * z1 + y0 * w1 + z0 * x1,
+            w0 * z1 + x0 * y1 - y0 * x1 + z0 * w1,
+            w0 * w1 - x0 * x1 - y0 * y1 - z0 * z1,
+        )
+    }
+
+    /// Rotates the vector `rhs` by `self`.
+    #[inline]
+    #[must_use]
+    pub fn mul_vec3(self, rhs: Vec3<T>) -> Vec3<T> {
+        let w = self.0.w;
+        let b = self.xyz();
+        let two = T::ONE + T::ONE;
+        rhs * (w * w - b.dot(b)) + b * (rhs.dot(b) * two) + b.cross(rhs) * (w * two)
+    }
+
+    /// Normalized linear interpolation between `self` and `end`.
+    ///
+    /// Interpolates along the shortest path and is cheaper than [`Self::slerp`], but does not
+    /// have a constant angular velocity. When `s` is `0` the result is `self` and when `s` is
+    /// `1` the result is `end` (or its negation).
+    #[must_use]
+    pub fn nlerp(self, end: Self, s: T) -> Self {
+        let end = if self.dot(end) < T::ZERO { -end } else { end };
+        Self(self.0 + (end.0 - self.0) * s).normalize()
+    }
+}
+
+impl<T> Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Epsilon
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    /// Creates the shortest rotation that rotates the direction `from` onto `to`.
+    ///
+    /// Both inputs must be normalized. When they point in opposite directions, the rotation is
+    /// 180 degrees around an arbitrary axis orthogonal to `from`.
+    #[must_use]
+    pub fn from_rotation_arc(from: Vec3<T>, to: Vec3<T>) -> Self {
+        let one_minus_eps = T::ONE - (T::EPSILON + T::EPSILON);
+        let dot = from.dot(to);
+        if dot > one_minus_eps {
+            Self::IDENTITY
+        } else if dot < -one_minus_eps {
+            let axis = from.any_orthonormal_vector();
+            Self::from_xyzw(axis.x, axis.y, axis.z, T::ZERO)
+        } else {
+            let c = from.cross(to);
+            Self::from_xyzw(c.x, c.y, c.z, T::ONE + dot).normalize()
+        }
+    }
+
+    /// Spherical linear interpolation between `self` and `end`.
+    ///
+    /// Interpolates along the shortest path with a constant angular velocity. When `s` is `0`
+    /// the result is `self` and when `s` is `1` the result is `end` (or its negation).
+    #[must_use]
+    pub fn slerp(self, end: Self, s: T) -> Self {
+        let mut dot = self.dot(end);
+        let end = if dot < T::ZERO {
+            dot = -dot;
+            -end
+        } else {
+            end
+        };
+
+        if dot > T::ONE - T::EPSILON {
+            // The angle is too small for the division by `sin(theta)`, so fall back to
+            // linear interpolation.
+            self.nlerp(end, s)
+        } else {
+            let theta = dot.acos();
+            let scale1 = (theta * (T::ONE - s)).sin();
+            let scale2 = (theta * s).sin();
+            Self((self.0 * scale1 + end.0 * scale2) / theta.sin())
+        }
+    }
+}
+
+impl<T> Deref for Quat<T> {
+    type Target = Vec4<T>;
+    #[inline]
+    fn deref(&self) -> &Vec4<T> {
+        &self.0
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Quat<T> {
+    type Output = Quat<T>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl<T> Mul<Quat<T>> for Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    type Output = Quat<T>;
+
+    #[inline]
+    fn mul(self, rhs: Quat<T>) -> Self::Output {
+        self.mul_quat(rhs)
+    }
+}
+impl<T> MulAssign<Quat<T>> for Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: Quat<T>) {
+        *self = self.mul_quat(rhs);
+    }
+}
+impl<T> Mul<Vec3<T>> for Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    type Output = Vec3<T>;
+
+    #[inline]
+    fn mul(self, rhs: Vec3<T>) -> Self::Output {
+        self.mul_vec3(rhs)
+    }
+}
+
+impl<T> From<Quat<T>> for Vec4<T> {
+    #[inline]
+    fn from(value: Quat<T>) -> Self {
+        value.0
+    }
+}
+impl<T> From<Quat<T>> for Mat3<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    #[inline]
+    fn from(value: Quat<T>) -> Self {
+        value.to_mat3()
+    }
+}
+impl<T> From<Quat<T>> for Mat4<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    #[inline]
+    fn from(value: Quat<T>) -> Self {
+        value.to_mat4()
+    }
+}
+impl<T> From<Mat3<T>> for Quat<T>
+where
+    T: Float
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    #[inline]
+    fn from(value: Mat3<T>) -> Self {
+        Self::from_mat3(&value)
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod quat_test {
+    use super::*;
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_approx_eq(a: &[f32], b: &[f32]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn quat_from_axis_angle() {
+        let q = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        assert_approx_eq(&q.mul_vec3(Vec3::X), &Vec3::Y);
+        assert_approx_eq(&(q * Vec3::Y), &Vec3::NEG_X);
+        assert_approx_eq(&q.to_mat3().mul_vec(Vec3::X), &Vec3::Y);
+    }
+    #[test]
+    fn quat_mul_quat() {
+        let a = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        let b = Quat::from_axis_angle(Vec3::X, FRAC_PI_2);
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_approx_eq(&(a * b * v), &(a * (b * v)));
+        assert_approx_eq(
+            &(a * b).to_mat3().mul_vec(v),
+            &(a.to_mat3() * b.to_mat3() * v),
+        );
+        assert_approx_eq(&(a * a.conjugate()), &Quat::<f32>::IDENTITY);
+        assert_approx_eq(&(a * a.inverse()), &Quat::<f32>::IDENTITY);
+    }
+    #[test]
+    fn quat_mat3_round_trip() {
+        for axis in [
+            Vec3::X,
+            Vec3::Y,
+            Vec3::Z,
+            Vec3::new(1.0, -2.0, 3.0).normalize(),
+        ] {
+            for angle in [0.0, 0.5, FRAC_PI_2, 2.0, PI, 4.0] {
+                let q = Quat::from_axis_angle(axis, angle);
+                let r = Quat::from_mat3(&q.to_mat3());
+                // q and -q describe the same rotation.
+                let r = if q.dot(r) < 0.0 { -r } else { r };
+                assert_approx_eq(&q, &r);
+                assert_approx_eq(&Quat::from_mat4(&q.to_mat4()).to_mat3().x, &q.to_mat3().x);
+            }
+        }
+    }
+    #[test]
+    fn quat_from_rotation_arc() {
+        let from = Vec3::new(1.0, 2.0, 2.0) / 3.0;
+        for to in [Vec3::X, Vec3::NEG_Y, from, -from] {
+            let q = Quat::from_rotation_arc(from, to);
+            assert_approx_eq(&(q * from), &to);
+            assert_approx_eq(&[q.length()], &[1.0]);
+        }
+    }
+    #[test]
+    fn quat_slerp() {
+        let a = Quat::<f32>::IDENTITY;
+        let b = Quat::from_axis_angle(Vec3::Y, FRAC_PI_2);
+        assert_approx_eq(&a.slerp(b, 0.0), &a);
+        assert_approx_eq(&a.slerp(b, 1.0), &b);
+        assert_approx_eq(
+            &a.slerp(b, 0.5),
+            &Quat::from_axis_angle(Vec3::Y, FRAC_PI_2 / 2.0),
+        );
+        assert_approx_eq(
+            &a.slerp(-b, 0.5),
+            &Quat::from_axis_angle(Vec3::Y, FRAC_PI_2 / 2.0),
+        );
+        assert_approx_eq(&a.slerp(a, 0.5), &a);
+        assert_approx_eq(&a.nlerp(b, 1.0), &b);
+    }
+}
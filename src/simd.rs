@@ -1,11 +1,26 @@
+//! `core::simd` support: the numeric traits for `Simd`, fast paths for `f32` vectors and
+//! conversions between slices of vectors and vectors of `Simd` lanes.
+//!
+//! With the `num-traits` feature `Simd` does not implement [`Zero`], [`One`], [`Compare`] and
+//! [`Float`], since those come from blanket impls over the num-traits types. Vectors of `Simd`
+//! lanes then lose the arithmetic helpers that need them, like `normalize`, `min` and `max`.
+
+use crate::bvec::*;
+use crate::kernel::*;
+use crate::mat::*;
 use crate::num::prelude::*;
-use core::simd::{LaneCount, Simd, SimdElement, SupportedLaneCount};
+use crate::vec::*;
+use core::ops::*;
+#[cfg(not(feature = "num-traits"))]
+use core::simd::Select;
+#[cfg(not(feature = "num-traits"))]
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::simd::{Mask, MaskElement, Simd, SimdElement, f32x4, f32x8, simd_swizzle};
 
 #[cfg(not(feature = "num-traits"))]
 impl<T, const N: usize> Zero for Simd<T, N>
 where
     T: SimdElement + Zero,
-    LaneCount<N>: SupportedLaneCount,
 {
     const ZERO: Simd<T, N> = Simd::splat(T::ZERO);
 }
@@ -13,14 +28,826 @@ where
 impl<T, const N: usize> One for Simd<T, N>
 where
     T: SimdElement + One,
-    LaneCount<N>: SupportedLaneCount,
 {
     const ONE: Simd<T, N> = Simd::splat(T::ONE);
 }
 impl<T, const N: usize> NegOne for Simd<T, N>
 where
     T: SimdElement + NegOne,
-    LaneCount<N>: SupportedLaneCount,
 {
     const NEG_ONE: Simd<T, N> = Simd::splat(T::NEG_ONE);
 }
+impl<T, const N: usize> Epsilon for Simd<T, N>
+where
+    T: SimdElement + Epsilon,
+{
+    const EPSILON: Simd<T, N> = Simd::splat(T::EPSILON);
+}
+impl<T, const N: usize> Pi for Simd<T, N>
+where
+    T: SimdElement + Pi,
+{
+    const PI: Simd<T, N> = Simd::splat(T::PI);
+}
+impl<T, const N: usize> Tau for Simd<T, N>
+where
+    T: SimdElement + Tau,
+{
+    const TAU: Simd<T, N> = Simd::splat(T::TAU);
+}
+impl<T, const N: usize> Infinity for Simd<T, N>
+where
+    T: SimdElement + Infinity,
+{
+    const INFINITY: Simd<T, N> = Simd::splat(T::INFINITY);
+}
+
+#[cfg(not(feature = "num-traits"))]
+macro_rules! impl_compare {
+    ($($type:ty)+) => {
+        $(
+            impl<const N: usize> Compare for Simd<$type, N> {
+                type Mask = Mask<<$type as SimdElement>::Mask, N>;
+
+                #[inline(always)]
+                fn cmplt(self, rhs: Self) -> Self::Mask { self.simd_lt(rhs) }
+                #[inline(always)]
+                fn cmple(self, rhs: Self) -> Self::Mask { self.simd_le(rhs) }
+                #[inline(always)]
+                fn cmpgt(self, rhs: Self) -> Self::Mask { self.simd_gt(rhs) }
+                #[inline(always)]
+                fn cmpge(self, rhs: Self) -> Self::Mask { self.simd_ge(rhs) }
+                #[inline(always)]
+                fn cmpeq(self, rhs: Self) -> Self::Mask { self.simd_eq(rhs) }
+                #[inline(always)]
+                fn cmpne(self, rhs: Self) -> Self::Mask { self.simd_ne(rhs) }
+
+                #[inline(always)]
+                fn select(mask: Self::Mask, if_true: Self, if_false: Self) -> Self {
+                    mask.select(if_true, if_false)
+                }
+
+                #[inline(always)]
+                fn all(mask: Self::Mask) -> bool { mask.all() }
+                #[inline(always)]
+                fn any(mask: Self::Mask) -> bool { mask.any() }
+            }
+        )+
+    };
+}
+
+#[cfg(not(feature = "num-traits"))]
+impl_compare!(usize u8 u16 u32 u64 isize i8 i16 i32 i64 f32 f64);
+
+/// Applies `f` to every lane of `v`.
+#[cfg(not(feature = "num-traits"))]
+#[inline(always)]
+fn map_lanes<T, U, const N: usize>(v: Simd<T, N>, f: impl FnMut(T) -> U) -> Simd<U, N>
+where
+    T: SimdElement,
+    U: SimdElement,
+{
+    Simd::from_array(v.to_array().map(f))
+}
+
+/// Applies `f` to every pair of lanes of `a` and `b`.
+#[cfg(not(feature = "num-traits"))]
+#[inline(always)]
+fn zip_lanes<T, const N: usize>(
+    a: Simd<T, N>,
+    b: Simd<T, N>,
+    mut f: impl FnMut(T, T) -> T,
+) -> Simd<T, N>
+where
+    T: SimdElement,
+{
+    Simd::from_array(core::array::from_fn(|i| f(a[i], b[i])))
+}
+
+/// Combines the lanes of `v` from first to last with `f`.
+#[inline(always)]
+fn reduce_lanes<T, const N: usize>(v: Simd<T, N>, f: impl FnMut(T, T) -> T) -> T
+where
+    T: SimdElement,
+{
+    // NOTE: There is always at least one lane.
+    v.to_array().into_iter().reduce(f).unwrap()
+}
+
+/// Every function is evaluated lane by lane with the [`Float`] implementation of `T`, so this is
+/// available for `no_std` targets as well.
+#[cfg(not(feature = "num-traits"))]
+impl<T, const N: usize> Float for Simd<T, N>
+where
+    T: SimdElement + Float,
+{
+    #[inline]
+    fn sqrt(self) -> Self {
+        map_lanes(self, T::sqrt)
+    }
+    #[inline]
+    fn hypot(self, other: Self) -> Self {
+        zip_lanes(self, other, T::hypot)
+    }
+
+    #[inline]
+    fn floor(self) -> Self {
+        map_lanes(self, T::floor)
+    }
+    #[inline]
+    fn ceil(self) -> Self {
+        map_lanes(self, T::ceil)
+    }
+    #[inline]
+    fn round(self) -> Self {
+        map_lanes(self, T::round)
+    }
+    #[inline]
+    fn trunc(self) -> Self {
+        map_lanes(self, T::trunc)
+    }
+    #[inline]
+    fn fract(self) -> Self {
+        map_lanes(self, T::fract)
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        map_lanes(self, T::abs)
+    }
+    #[inline]
+    fn signum(self) -> Self {
+        map_lanes(self, T::signum)
+    }
+    #[inline]
+    fn copysign(self, sign: Self) -> Self {
+        zip_lanes(self, sign, T::copysign)
+    }
+    #[inline]
+    fn recip(self) -> Self {
+        map_lanes(self, T::recip)
+    }
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Simd::from_array(core::array::from_fn(|i| self[i].mul_add(a[i], b[i])))
+    }
+
+    #[inline]
+    fn sin(self) -> Self {
+        map_lanes(self, T::sin)
+    }
+    #[inline]
+    fn cos(self) -> Self {
+        map_lanes(self, T::cos)
+    }
+    #[inline]
+    fn tan(self) -> Self {
+        map_lanes(self, T::tan)
+    }
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+    #[inline]
+    fn asin(self) -> Self {
+        map_lanes(self, T::asin)
+    }
+    #[inline]
+    fn acos(self) -> Self {
+        map_lanes(self, T::acos)
+    }
+    #[inline]
+    fn atan(self) -> Self {
+        map_lanes(self, T::atan)
+    }
+    #[inline]
+    fn atan2(self, other: Self) -> Self {
+        zip_lanes(self, other, T::atan2)
+    }
+
+    #[inline]
+    fn exp(self) -> Self {
+        map_lanes(self, T::exp)
+    }
+    #[inline]
+    fn ln(self) -> Self {
+        map_lanes(self, T::ln)
+    }
+    #[inline]
+    fn powf(self, n: Self) -> Self {
+        zip_lanes(self, n, T::powf)
+    }
+    #[inline]
+    fn powi(self, n: i32) -> Self {
+        map_lanes(self, |x| x.powi(n))
+    }
+
+    /// Returns `true` if any lane is NaN.
+    #[inline]
+    fn is_nan(self) -> bool {
+        self.to_array().into_iter().any(T::is_nan)
+    }
+    /// Returns `true` only if every lane is finite.
+    #[inline]
+    fn is_finite(self) -> bool {
+        self.to_array().into_iter().all(T::is_finite)
+    }
+}
+
+/// Implements the structure-of-arrays helpers for vectors with `Simd` elements.
+macro_rules! impl_simd_vec {
+    ($($Vec:ident ($($get:tt),+))+) => {
+        $(
+            impl<T, const N: usize> $Vec<Simd<T, N>>
+            where
+                T: SimdElement,
+            {
+                /// Creates a vector with `value` in every lane.
+                #[inline]
+                #[must_use]
+                pub fn splat_from(value: $Vec<T>) -> Self {
+                    Self { $($get: Simd::splat(value.$get)),+ }
+                }
+
+                /// Returns the vector stored in `lane`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `lane` is not less than `N`.
+                #[inline]
+                #[must_use]
+                pub fn extract_lane(&self, lane: usize) -> $Vec<T> {
+                    $Vec { $($get: self.$get[lane]),+ }
+                }
+
+                /// Returns the sum of all lanes.
+                #[inline]
+                #[must_use]
+                pub fn reduce_sum(self) -> $Vec<T> where T: Add<Output = T> {
+                    $Vec { $($get: reduce_lanes(self.$get, T::add)),+ }
+                }
+                /// Returns the product of all lanes.
+                #[inline]
+                #[must_use]
+                pub fn reduce_product(self) -> $Vec<T> where T: Mul<Output = T> {
+                    $Vec { $($get: reduce_lanes(self.$get, T::mul)),+ }
+                }
+                /// Returns the component-wise minimum of all lanes.
+                #[inline]
+                #[must_use]
+                pub fn reduce_min(self) -> $Vec<T> where T: Compare + Copy {
+                    $Vec { $($get: reduce_lanes(self.$get, |a, b| T::select(a.cmplt(b), a, b))),+ }
+                }
+                /// Returns the component-wise maximum of all lanes.
+                #[inline]
+                #[must_use]
+                pub fn reduce_max(self) -> $Vec<T> where T: Compare + Copy {
+                    $Vec { $($get: reduce_lanes(self.$get, |a, b| T::select(a.cmpgt(b), a, b))),+ }
+                }
+            }
+        )+
+    };
+}
+
+impl_simd_vec!(Vec2 (x, y) Vec3 (x, y, z) Vec4 (x, y, z, w));
+
+macro_rules! impl_simd_bvec {
+    ($($BVec:ident ($($get:tt),+))+) => {
+        $(
+            impl<T, const N: usize> $BVec<Mask<T, N>>
+            where
+                T: MaskElement,
+            {
+                /// Returns the mask stored in `lane`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `lane` is not less than `N`.
+                #[inline]
+                #[must_use]
+                pub fn extract_lane(&self, lane: usize) -> $BVec<bool> {
+                    $BVec { $($get: self.$get.test(lane)),+ }
+                }
+            }
+        )+
+    };
+}
+
+impl_simd_bvec!(BVec2 (x, y) BVec3 (x, y, z) BVec4 (x, y, z, w));
+
+/// Conversion between a slice of vectors (array-of-structures) and a single vector with `Simd`
+/// lanes (structure-of-arrays), such as `&[Vec3<f32>]` and `Vec3<f32x8>`.
+pub trait Lanes<V>: Sized {
+    /// The number of vectors stored in `Self`.
+    const LANES: usize;
+
+    /// Loads the first [`Self::LANES`] vectors of `src`, lanes past the end of `src` are set to
+    /// `fill`.
+    fn gather_or(src: &[V], fill: V) -> Self;
+    /// Stores the lanes of `self` into `dst`, lanes past the end of `dst` are discarded.
+    fn scatter(&self, dst: &mut [V]);
+}
+
+macro_rules! impl_lanes {
+    ($($Vec:ident ($($get:tt),+))+) => {
+        $(
+            impl<T, const N: usize> Lanes<$Vec<T>> for $Vec<Simd<T, N>>
+            where
+                T: SimdElement,
+            {
+                const LANES: usize = N;
+
+                #[inline]
+                fn gather_or(src: &[$Vec<T>], fill: $Vec<T>) -> Self {
+                    let lane = |i: usize| src.get(i).copied().unwrap_or(fill);
+                    Self { $($get: Simd::from_array(core::array::from_fn(|i| lane(i).$get))),+ }
+                }
+
+                #[inline]
+                fn scatter(&self, dst: &mut [$Vec<T>]) {
+                    for (i, out) in dst.iter_mut().take(N).enumerate() {
+                        *out = self.extract_lane(i);
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_lanes!(Vec2 (x, y) Vec3 (x, y, z) Vec4 (x, y, z, w));
+
+/// Packs `src` into wide vectors of [`Lanes::LANES`] vectors each.
+///
+/// If the length of `src` is not a multiple of the lane count, the lanes of the last wide vector
+/// past the end of `src` are set to `fill`. Use [`tail_mask`] to ignore them.
+pub fn pack<V, W>(src: &[V], fill: V) -> impl Iterator<Item = W> + '_
+where
+    V: Copy,
+    W: Lanes<V>,
+{
+    src.chunks(W::LANES)
+        .map(move |chunk| W::gather_or(chunk, fill))
+}
+
+/// Unpacks the wide vectors in `src` into `dst`, the inverse of [`pack`].
+///
+/// Padding lanes past the end of `dst` are discarded.
+pub fn unpack<V, W>(src: &[W], dst: &mut [V])
+where
+    W: Lanes<V>,
+{
+    for (wide, chunk) in src.iter().zip(dst.chunks_mut(W::LANES)) {
+        wide.scatter(chunk);
+    }
+}
+
+/// Returns the lanes of the `index`-th wide vector produced by [`pack`] that hold one of the `len`
+/// source vectors, the other lanes are padding.
+#[inline]
+pub fn tail_mask<M, const N: usize>(len: usize, index: usize) -> Mask<M, N>
+where
+    M: MaskElement,
+{
+    let start = index * N;
+    Mask::from_array(core::array::from_fn(|i| start + i < len))
+}
+
+impl<T: SimdElement> From<Vec4<T>> for Simd<T, 4> {
+    #[inline]
+    fn from(value: Vec4<T>) -> Self {
+        Simd::from_array(value.into_array())
+    }
+}
+impl<T: SimdElement> From<Simd<T, 4>> for Vec4<T> {
+    #[inline]
+    fn from(value: Simd<T, 4>) -> Self {
+        Vec4::from(value.to_array())
+    }
+}
+/// The padding lane is set to zero.
+impl<T: SimdElement + Zero> From<Vec3A<T>> for Simd<T, 4> {
+    #[inline]
+    fn from(value: Vec3A<T>) -> Self {
+        Simd::from_array([value.x, value.y, value.z, T::ZERO])
+    }
+}
+/// The last lane is discarded.
+impl<T: SimdElement> From<Simd<T, 4>> for Vec3A<T> {
+    #[inline]
+    fn from(value: Simd<T, 4>) -> Self {
+        let [x, y, z, _] = value.to_array();
+        Vec3A::new(x, y, z)
+    }
+}
+
+// NOTE: The fast paths below must stay bit-compatible with the generic code in `impl_vec!` and
+// `impl_mat!`, so horizontal sums are added lane by lane in order instead of using `reduce_sum`.
+
+impl VecKernel<f32> for Vec4<f32> {
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        (f32x4::from(self) + f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        (f32x4::from(self) - f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        (f32x4::from(self) * f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        (f32x4::from(self) / f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn dot(self, rhs: Self) -> f32 {
+        let [x, y, z, w] = (f32x4::from(self) * f32x4::from(rhs)).to_array();
+        x + y + z + w
+    }
+}
+
+impl VecKernel<f32> for Vec3A<f32> {
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        (f32x4::from(self) + f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        (f32x4::from(self) - f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        (f32x4::from(self) * f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        // NOTE: The padding lane computes 0/0, which is discarded.
+        (f32x4::from(self) / f32x4::from(rhs)).into()
+    }
+    #[inline]
+    fn dot(self, rhs: Self) -> f32 {
+        let [x, y, z, _] = (f32x4::from(self) * f32x4::from(rhs)).to_array();
+        x + y + z
+    }
+}
+
+impl MatKernel<f32> for Mat4<f32> {
+    #[inline]
+    fn mul_mat(&self, rhs: &Self) -> Self {
+        let [c0, c1, c2, c3] = [self.x, self.y, self.z, self.w].map(f32x4::from);
+        let mul_vec = |v: Vec4<f32>| -> Vec4<f32> {
+            (c0 * f32x4::splat(v.x)
+                + c1 * f32x4::splat(v.y)
+                + c2 * f32x4::splat(v.z)
+                + c3 * f32x4::splat(v.w))
+            .into()
+        };
+        Mat4::from_cols(
+            mul_vec(rhs.x),
+            mul_vec(rhs.y),
+            mul_vec(rhs.z),
+            mul_vec(rhs.w),
+        )
+    }
+
+    #[inline]
+    fn inverse(&self) -> Self {
+        // Same cofactor scheme as the generic `Mat4::adjugate`, but with the rows of `self` as
+        // registers so the 2x2 sub-determinants of two rows come out as one vector.
+        let rows: [f32x4; 4] = core::array::from_fn(|i| {
+            f32x4::from_array([self.x[i], self.y[i], self.z[i], self.w[i]])
+        });
+        // Lane i of `fac` holds the sub-determinant of rows (r, s) and columns (a, b), where
+        // (a, b) is one of (2, 3), (2, 3), (1, 3), (1, 2).
+        let fac = |r: usize, s: usize| {
+            let a_r = simd_swizzle!(rows[r], [2, 2, 1, 1]);
+            let b_r = simd_swizzle!(rows[r], [3, 3, 3, 2]);
+            let a_s = simd_swizzle!(rows[s], [2, 2, 1, 1]);
+            let b_s = simd_swizzle!(rows[s], [3, 3, 3, 2]);
+            a_r * b_s - b_r * a_s
+        };
+        let fac0 = fac(2, 3);
+        let fac1 = fac(1, 3);
+        let fac2 = fac(1, 2);
+        let fac3 = fac(0, 3);
+        let fac4 = fac(0, 2);
+        let fac5 = fac(0, 1);
+
+        let vec0 = simd_swizzle!(rows[0], [1, 0, 0, 0]);
+        let vec1 = simd_swizzle!(rows[1], [1, 0, 0, 0]);
+        let vec2 = simd_swizzle!(rows[2], [1, 0, 0, 0]);
+        let vec3 = simd_swizzle!(rows[3], [1, 0, 0, 0]);
+
+        let sign_a = f32x4::from_array([1.0, -1.0, 1.0, -1.0]);
+        let sign_b = f32x4::from_array([-1.0, 1.0, -1.0, 1.0]);
+        let inv0 = (vec1 * fac0 - vec2 * fac1 + vec3 * fac2) * sign_a;
+        let inv1 = (vec0 * fac0 - vec2 * fac3 + vec3 * fac4) * sign_b;
+        let inv2 = (vec0 * fac1 - vec1 * fac3 + vec3 * fac5) * sign_a;
+        let inv3 = (vec0 * fac2 - vec1 * fac4 + vec2 * fac5) * sign_b;
+
+        let [d0, d1, d2, d3] = (rows[0] * inv0).to_array();
+        let inv_det = f32x4::splat(1.0 / (d0 + d1 + d2 + d3));
+        Mat4::from_cols(
+            (inv0 * inv_det).into(),
+            (inv1 * inv_det).into(),
+            (inv2 * inv_det).into(),
+            (inv3 * inv_det).into(),
+        )
+    }
+}
+
+/// Transforms `points` eight at a time as `Vec3<f32x8>`, adding the translation of `m` only if
+/// `translate` is set.
+#[inline]
+fn transform_f32x8(m: &Mat4<f32>, points: &mut [Vec3<f32>], translate: bool) {
+    let col = |c: Vec4<f32>| Vec3::<f32x8>::splat_from(c.truncate());
+    let (x, y, z, w) = (col(m.x), col(m.y), col(m.z), col(m.w));
+    for chunk in points.chunks_mut(8) {
+        let p = Vec3::<f32x8>::gather_or(chunk, Vec3::ZERO);
+        let mut res = x * p.x + y * p.y + z * p.z;
+        if translate {
+            res += w;
+        }
+        res.scatter(chunk);
+    }
+}
+
+impl TransformKernel<f32> for Mat4<f32> {
+    #[inline]
+    fn transform_points(&self, points: &mut [Vec3<f32>]) {
+        transform_f32x8(self, points, true);
+    }
+    #[inline]
+    fn transform_vectors(&self, vectors: &mut [Vec3<f32>]) {
+        transform_f32x8(self, vectors, false);
+    }
+}
+
+#[cfg(test)]
+mod simd_test {
+    use super::*;
+
+    /// An `f32` that does not hit the SIMD specializations, to compare against the generic path.
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    struct Scalar(f32);
+
+    macro_rules! impl_scalar_op {
+        ($($Op:ident $op:ident),+) => {
+            $(
+                impl $Op for Scalar {
+                    type Output = Scalar;
+                    fn $op(self, rhs: Scalar) -> Scalar {
+                        Scalar(self.0.$op(rhs.0))
+                    }
+                }
+            )+
+        };
+    }
+    impl_scalar_op!(Add add, Sub sub, Mul mul, Div div);
+    impl Neg for Scalar {
+        type Output = Scalar;
+        fn neg(self) -> Scalar {
+            Scalar(-self.0)
+        }
+    }
+    impl Zero for Scalar {
+        const ZERO: Self = Scalar(0.0);
+    }
+    impl One for Scalar {
+        const ONE: Self = Scalar(1.0);
+    }
+    macro_rules! delegate {
+        ($($fn:ident($($arg:ident),*))+) => {
+            $(
+                fn $fn(self, $($arg: Self),*) -> Self {
+                    Scalar(Float::$fn(self.0, $($arg.0),*))
+                }
+            )+
+        };
+    }
+    impl Float for Scalar {
+        delegate! {
+            sqrt() hypot(other) floor() ceil() round() trunc() fract()
+            abs() signum() copysign(sign) recip() mul_add(a, b)
+            sin() cos() tan() asin() acos() atan() atan2(other) exp() ln() powf(n)
+        }
+        fn sin_cos(self) -> (Self, Self) {
+            let (sin, cos) = Float::sin_cos(self.0);
+            (Scalar(sin), Scalar(cos))
+        }
+        fn powi(self, n: i32) -> Self {
+            Scalar(Float::powi(self.0, n))
+        }
+        fn is_nan(self) -> bool {
+            Float::is_nan(self.0)
+        }
+        fn is_finite(self) -> bool {
+            Float::is_finite(self.0)
+        }
+    }
+
+    /// Deterministic pseudo-random values in `[-8, 8)`.
+    fn values(seed: u32) -> impl Iterator<Item = f32> {
+        let mut state = seed;
+        core::iter::repeat_with(move || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f32 / (1 << 20) as f32 - 8.0
+        })
+    }
+    fn vec4(values: &mut impl Iterator<Item = f32>) -> Vec4<f32> {
+        Vec4::from(core::array::from_fn::<_, 4, _>(|_| values.next().unwrap()))
+    }
+    fn vec3a(values: &mut impl Iterator<Item = f32>) -> Vec3A<f32> {
+        Vec3A::from(core::array::from_fn::<_, 3, _>(|_| values.next().unwrap()))
+    }
+    fn mat4(values: &mut impl Iterator<Item = f32>) -> Mat4<f32> {
+        Mat4::from_cols(vec4(values), vec4(values), vec4(values), vec4(values))
+    }
+    fn assert_bits_eq(simd: &[f32], scalar: &[Scalar]) {
+        assert_eq!(simd.len(), scalar.len());
+        assert!(
+            simd.iter()
+                .zip(scalar)
+                .all(|(a, b)| a.to_bits() == b.0.to_bits()),
+            "{simd:?} != {scalar:?}"
+        );
+    }
+
+    #[test]
+    fn vec4_matches_scalar() {
+        let values = &mut values(1);
+        for _ in 0..256 {
+            let (a, b) = (vec4(values), vec4(values));
+            let (sa, sb) = (a.map(Scalar), b.map(Scalar));
+            assert_bits_eq(&(a + b), &(sa + sb));
+            assert_bits_eq(&(a - b), &(sa - sb));
+            assert_bits_eq(&(a * b), &(sa * sb));
+            assert_bits_eq(&(a / b), &(sa / sb));
+            assert_bits_eq(&[a.dot(b)], &[sa.dot(sb)]);
+            assert_bits_eq(&[a.length()], &[sa.length()]);
+            assert_bits_eq(&a.normalize(), &sa.normalize());
+        }
+    }
+
+    #[test]
+    fn vec3a_matches_scalar() {
+        let values = &mut values(2);
+        for _ in 0..256 {
+            let (a, b) = (vec3a(values), vec3a(values));
+            let (sa, sb) = (a.map(Scalar), b.map(Scalar));
+            assert_bits_eq(&(a + b), &(sa + sb));
+            assert_bits_eq(&(a - b), &(sa - sb));
+            assert_bits_eq(&(a * b), &(sa * sb));
+            assert_bits_eq(&(a / b), &(sa / sb));
+            assert_bits_eq(&[a.dot(b)], &[sa.dot(sb)]);
+            assert_bits_eq(&[a.length()], &[sa.length()]);
+            assert_bits_eq(&a.normalize(), &sa.normalize());
+        }
+    }
+
+    #[test]
+    fn mat4_matches_scalar() {
+        let values = &mut values(3);
+        for _ in 0..256 {
+            let (a, b) = (mat4(values), mat4(values));
+            let (sa, sb) = (a.map(Scalar), b.map(Scalar));
+            for (simd, scalar) in [(a * b, sa * sb), (a.inverse(), sa.inverse())] {
+                assert_bits_eq(&simd.x, &scalar.x);
+                assert_bits_eq(&simd.y, &scalar.y);
+                assert_bits_eq(&simd.z, &scalar.z);
+                assert_bits_eq(&simd.w, &scalar.w);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "num-traits"))]
+    fn soa_matches_lanes() {
+        use core::simd::f32x8;
+
+        let values = &mut values(4);
+        let lanes: [Vec3<f32>; 8] =
+            core::array::from_fn(|_| Vec3::from(core::array::from_fn(|_| values.next().unwrap())));
+        let wide = Vec3::new(
+            f32x8::from_array(lanes.map(|v| v.x)),
+            f32x8::from_array(lanes.map(|v| v.y)),
+            f32x8::from_array(lanes.map(|v| v.z)),
+        );
+        let other = Vec3::splat_from(lanes[0]);
+        for (i, lane) in lanes.into_iter().enumerate() {
+            assert_eq!(wide.extract_lane(i), lane);
+            assert_eq!(wide.normalize().extract_lane(i), lane.normalize());
+            assert_eq!(wide.min(other).extract_lane(i), lane.min(lanes[0]));
+            assert_eq!(wide.max(other).extract_lane(i), lane.max(lanes[0]));
+            assert_eq!(wide.min_element()[i], lane.min_element());
+            assert_eq!(wide.dot(other)[i], lane.dot(lanes[0]));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "num-traits"))]
+    fn soa_length_helpers_are_lane_wise() {
+        let lanes = [
+            Vec2::new(3.0, 4.0),
+            Vec2::new(0.5, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(-1.0, 1.0),
+        ];
+        let wide = Vec2::new(
+            f32x4::from_array(lanes.map(|v| v.x)),
+            f32x4::from_array(lanes.map(|v| v.y)),
+        );
+        let other = Vec2::new(1.0, 0.0);
+        let wide_other = Vec2::splat_from(other);
+        for (i, lane) in lanes.into_iter().enumerate() {
+            assert_eq!(
+                wide.normalize_or(wide_other).extract_lane(i),
+                lane.normalize_or(other)
+            );
+            // NOTE: Both are undefined for the zero length lane.
+            if i != 2 {
+                let (min, max) = (f32x4::splat(1.0), f32x4::splat(2.0));
+                assert_eq!(
+                    wide.clamp_length(min, max).extract_lane(i),
+                    lane.clamp_length(1.0, 2.0)
+                );
+                assert_eq!(wide.angle_between(wide_other)[i], lane.angle_between(other));
+            }
+        }
+        assert_eq!(wide.try_normalize(), None);
+        assert!(!wide.is_normalized());
+        assert!(Vec2::<f32x4>::splat_from(Vec2::new(0.6, 0.8)).is_normalized());
+    }
+
+    #[test]
+    fn soa_reductions() {
+        use core::simd::i32x4;
+
+        let wide = Vec2::new(
+            i32x4::from_array([3, -1, 4, 1]),
+            i32x4::from_array([-5, 9, 2, -6]),
+        );
+        assert_eq!(wide.reduce_sum(), Vec2::new(7, 0));
+        assert_eq!(wide.reduce_product(), Vec2::new(-12, 540));
+        assert_eq!(wide.reduce_min(), Vec2::new(-1, -6));
+        assert_eq!(wide.reduce_max(), Vec2::new(4, 9));
+        assert_eq!(
+            Vec2::splat_from(Vec2::new(1, 2)),
+            Vec2::new(i32x4::splat(1), i32x4::splat(2))
+        );
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let src: [Vec3<f32>; 11] = core::array::from_fn(|i| Vec3::splat(i as f32));
+        let fill = Vec3::splat(-1.0);
+        let wide: Vec<Vec3<f32x4>> = pack(&src, fill).collect();
+        assert_eq!(wide.len(), 3);
+        assert_eq!(wide[1].extract_lane(2), src[6]);
+        assert_eq!(wide[2].extract_lane(3), fill);
+
+        assert_eq!(tail_mask::<i32, 4>(src.len(), 1).to_array(), [true; 4]);
+        assert_eq!(
+            tail_mask::<i32, 4>(src.len(), 2).to_array(),
+            [true, true, true, false]
+        );
+
+        let mut dst = [Vec3::ZERO; 11];
+        unpack(&wide, &mut dst);
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    fn transform_slices_match_scalar() {
+        let values = &mut values(5);
+        let m = mat4(values);
+        let sm = m.map(Scalar);
+        let input: [Vec3<f32>; 19] =
+            core::array::from_fn(|_| Vec3::from(core::array::from_fn(|_| values.next().unwrap())));
+        let (mut points, mut vectors) = ([Vec3::ZERO; 19], [Vec3::ZERO; 19]);
+        m.transform_points(&input, &mut points);
+        m.transform_vectors(&input, &mut vectors);
+        for i in 0..input.len() {
+            let p = input[i].map(Scalar);
+            assert_bits_eq(&points[i], &sm.transform_point3(p));
+            assert_bits_eq(&vectors[i], &sm.transform_vector3(p));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "num-traits"))]
+    fn soa_cmp_select() {
+        let a = Vec2::new(f32x4::from_array([1.0, 2.0, 3.0, 4.0]), f32x4::splat(0.0));
+        let b = Vec2::new(f32x4::splat(2.5), f32x4::from_array([1.0, -1.0, 0.0, 0.0]));
+        let mask = a.cmplt(b);
+        assert_eq!(mask.extract_lane(0), BVec2::new(true, true));
+        assert_eq!(mask.extract_lane(3), BVec2::new(false, false));
+        assert_eq!(mask.any().to_array(), [true, true, false, false]);
+        assert_eq!(mask.all().to_array(), [true, false, false, false]);
+        assert_eq!(Vec2::select(mask, a, b), a.min(b));
+        assert!(f32x4::any(mask.x) && !f32x4::all(mask.x));
+        assert!(f32x4::all(a.x.cmpgt(f32x4::splat(0.0))));
+        assert!(!f32x4::any(a.y.cmpne(f32x4::splat(0.0))));
+    }
+}
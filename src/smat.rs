@@ -0,0 +1,412 @@
+use crate::mat::*;
+use crate::num::prelude::*;
+use crate::svec::*;
+use core::array;
+use core::ops::*;
+
+/// Implements a single operation for a [`SMatrix`].
+///
+/// Like the named matrices the right-hand side is a scalar or a matrix of the same shape, the
+/// `@scalar` arm only implements the former.
+macro_rules! impl_op {
+    (impl $Op:ident for SMatrix { $op:ident }) => {
+        impl_op!(@scalar impl $Op for SMatrix { $op });
+        impl<T, const R: usize, const C: usize> $Op<SMatrix<T, R, C>> for SMatrix<T, R, C>
+            where T: $Op<Output = T> + Copy
+        {
+            type Output = SMatrix<T, R, C>;
+            #[inline]
+            fn $op(self, rhs: SMatrix<T, R, C>) -> Self::Output {
+                SMatrix(zip_map(self.0, rhs.0, SVector::$op))
+            }
+        }
+        impl_array_ref_op!(@array impl $Op for SMatrix [R, C] { $op });
+    };
+    (@scalar impl $Op:ident for SMatrix { $op:ident }) => {
+        impl<T, const R: usize, const C: usize> $Op<T> for SMatrix<T, R, C>
+            where T: $Op<Output = T> + Copy
+        {
+            type Output = SMatrix<T, R, C>;
+            #[inline]
+            fn $op(self, rhs: T) -> Self::Output {
+                SMatrix(self.0.map(|col| col.$op(rhs)))
+            }
+        }
+        impl_array_ref_op!(@scalar impl $Op for SMatrix [R, C] { $op });
+    };
+}
+
+/// Implements a single assign operation for a [`SMatrix`].
+///
+/// The `@scalar` arm only implements the scalar right-hand side.
+macro_rules! impl_assign_op {
+    (impl $Op:ident for SMatrix { $op:ident }) => {
+        impl_assign_op!(@scalar impl $Op for SMatrix { $op });
+        impl<T, const R: usize, const C: usize> $Op<SMatrix<T, R, C>> for SMatrix<T, R, C>
+        where
+            T: $Op<T>,
+        {
+            #[inline]
+            fn $op(&mut self, rhs: SMatrix<T, R, C>) {
+                for (a, b) in self.0.iter_mut().zip(rhs.0) {
+                    a.$op(b);
+                }
+            }
+        }
+    };
+    (@scalar impl $Op:ident for SMatrix { $op:ident }) => {
+        impl<T, const R: usize, const C: usize> $Op<T> for SMatrix<T, R, C>
+        where
+            T: $Op<T> + Copy,
+        {
+            #[inline]
+            fn $op(&mut self, rhs: T) {
+                for a in &mut self.0 {
+                    a.$op(rhs);
+                }
+            }
+        }
+    };
+}
+
+/// A matrix with `R` rows and `C` columns, for shapes not covered by [`Mat2`], [`Mat3`] and
+/// [`Mat4`].
+///
+/// Like the named matrices it is stored in column-major order, each of the `C` columns being a
+/// [`SVector`] of `R` elements.
+#[derive(Debug, Clone, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct SMatrix<T, const R: usize, const C: usize>(pub [SVector<T, R>; C]);
+
+impl<T: Copy, const R: usize, const C: usize> Copy for SMatrix<T, R, C> {}
+
+impl<T: Eq, const R: usize, const C: usize> Eq for SMatrix<T, R, C> {}
+
+impl<T: Default, const R: usize, const C: usize> Default for SMatrix<T, R, C> {
+    #[inline]
+    fn default() -> Self {
+        Self(array::from_fn(|_| SVector::default()))
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable, const R: usize, const C: usize> bytemuck::Zeroable
+    for SMatrix<T, R, C>
+{
+}
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod, const R: usize, const C: usize> bytemuck::Pod for SMatrix<T, R, C> {}
+
+impl<T: Zero, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub const ZERO: Self = Self([SVector::ZERO; C]);
+}
+impl<T: One, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub const ONE: Self = Self([SVector::ONE; C]);
+}
+impl<T: NegOne, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub const NEG_ONE: Self = Self([SVector::NEG_ONE; C]);
+}
+impl<T: Zero + One + Copy, const R: usize, const C: usize> SMatrix<T, R, C> {
+    /// The matrix with ones on the main diagonal and zeros everywhere else, also for
+    /// non-square shapes.
+    pub const IDENTITY: Self = {
+        let mut out = Self::ZERO;
+        let mut i = 0;
+        while i < R && i < C {
+            out.0[i].0[i] = T::ONE;
+            i += 1;
+        }
+        out
+    };
+}
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub const fn from_cols(cols: [SVector<T, R>; C]) -> Self {
+        Self(cols)
+    }
+
+    pub fn map<D, F>(self, mut f: F) -> SMatrix<D, R, C>
+    where
+        F: FnMut(T) -> D,
+    {
+        SMatrix(self.0.map(|col| col.map(&mut f)))
+    }
+
+    pub fn apply<F>(&mut self, f: F)
+    where
+        T: Copy,
+        F: FnMut(T) -> T,
+    {
+        *self = self.map(f);
+    }
+
+    /// Returns the transpose of `self`.
+    #[inline]
+    #[must_use]
+    pub fn transpose(&self) -> SMatrix<T, C, R>
+    where
+        T: Copy,
+    {
+        SMatrix(array::from_fn(|i| {
+            SVector(array::from_fn(|j| self.0[j].0[i]))
+        }))
+    }
+
+    /// Transforms the column vector `rhs` by `self`.
+    ///
+    /// The result is the linear combination of the columns of `self` weighted by the elements
+    /// of `rhs`.
+    #[inline]
+    #[must_use]
+    pub fn mul_vec(&self, rhs: SVector<T, C>) -> SVector<T, R>
+    where
+        T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+    {
+        zip_map(self.0, rhs.0, SVector::mul)
+            .into_iter()
+            .fold(SVector::ZERO, SVector::add)
+    }
+
+    /// Computes the matrix product of `self` and `rhs`.
+    ///
+    /// Matrices are stored in column-major order, so the result applies `rhs` first and `self`
+    /// second when transforming a column vector.
+    #[inline]
+    #[must_use]
+    pub fn mul_mat<const K: usize>(&self, rhs: &SMatrix<T, C, K>) -> SMatrix<T, R, K>
+    where
+        T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+    {
+        SMatrix(rhs.0.map(|col| self.mul_vec(col)))
+    }
+
+    /// Multiplies each element of `self` with the corresponding element of `rhs`.
+    ///
+    /// This is the Hadamard product, use [`Self::mul_mat`] or the `*` operator for matrix
+    /// multiplication.
+    #[inline]
+    #[must_use]
+    pub fn mul_elementwise(self, rhs: Self) -> Self
+    where
+        T: Mul<Output = T> + Copy,
+    {
+        Self(zip_map(self.0, rhs.0, SVector::mul))
+    }
+}
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> {
+    impl_cast!(@as SMatrix [R, C]
+        as_usize usize, as_u8 u8, as_u16 u16, as_u32 u32, as_u64 u64, as_u128 u128,
+        as_isize isize, as_i8 i8, as_i16 i16, as_i32 i32, as_i64 i64, as_i128 i128,
+        as_f32 f32, as_f64 f64
+    );
+    impl_cast!(@lossless SMatrix [R, C]);
+}
+
+impl<T, U, const R: usize, const C: usize> CheckedCast<SMatrix<U, R, C>> for SMatrix<T, R, C>
+where
+    T: CheckedCast<U>,
+{
+    #[inline]
+    fn checked_cast(self) -> Option<SMatrix<U, R, C>> {
+        let out = self.0.map(SVector::checked_cast);
+        if out.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(SMatrix(out.map(Option::unwrap)))
+    }
+}
+
+impl_op!(impl Add for SMatrix { add });
+impl_op!(impl Sub for SMatrix { sub });
+// NOTE: Matrix-matrix and matrix-vector multiplication are linear algebra products, not element-wise.
+impl_op!(@scalar impl Mul for SMatrix { mul });
+impl_op!(impl Div for SMatrix { div });
+impl_op!(impl Rem for SMatrix { rem });
+impl_assign_op!(impl AddAssign for SMatrix { add_assign });
+impl_assign_op!(impl SubAssign for SMatrix { sub_assign });
+impl_assign_op!(@scalar impl MulAssign for SMatrix { mul_assign });
+impl_assign_op!(impl DivAssign for SMatrix { div_assign });
+impl_assign_op!(impl RemAssign for SMatrix { rem_assign });
+impl_array_prim!(SMatrix [R, C], usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64
+    => (Add add, Sub sub, Mul mul, Div div, Rem rem));
+
+impl<T, const R: usize, const C: usize, const K: usize> Mul<SMatrix<T, C, K>> for SMatrix<T, R, C>
+where
+    T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+{
+    type Output = SMatrix<T, R, K>;
+    #[inline]
+    fn mul(self, rhs: SMatrix<T, C, K>) -> Self::Output {
+        self.mul_mat(&rhs)
+    }
+}
+impl<T, const R: usize, const C: usize> Mul<SVector<T, C>> for SMatrix<T, R, C>
+where
+    T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+{
+    type Output = SVector<T, R>;
+    #[inline]
+    fn mul(self, rhs: SVector<T, C>) -> Self::Output {
+        self.mul_vec(rhs)
+    }
+}
+impl<T, const R: usize, const C: usize> Mul<SMatrix<T, R, C>> for SVector<T, R>
+where
+    T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+{
+    type Output = SVector<T, C>;
+    /// Transforms the row vector `self` by `rhs`, which is equivalent to multiplying the
+    /// transpose of `rhs` with `self`.
+    #[inline]
+    fn mul(self, rhs: SMatrix<T, R, C>) -> Self::Output {
+        SVector(rhs.0.map(|col| self.dot(col)))
+    }
+}
+impl<T, const N: usize> MulAssign<SMatrix<T, N, N>> for SMatrix<T, N, N>
+where
+    T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: SMatrix<T, N, N>) {
+        *self = self.mul_mat(&rhs);
+    }
+}
+
+impl<T: Neg<Output = T>, const R: usize, const C: usize> Neg for SMatrix<T, R, C> {
+    type Output = SMatrix<T, R, C>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        SMatrix(self.0.map(SVector::neg))
+    }
+}
+
+/// Indexes the columns of the matrix.
+impl<T, const R: usize, const C: usize> Index<usize> for SMatrix<T, R, C> {
+    type Output = SVector<T, R>;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+impl<T, const R: usize, const C: usize> IndexMut<usize> for SMatrix<T, R, C> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+/// Converts from an array of columns.
+impl<T, const R: usize, const C: usize> From<[[T; R]; C]> for SMatrix<T, R, C> {
+    #[inline]
+    fn from(value: [[T; R]; C]) -> Self {
+        Self(value.map(SVector))
+    }
+}
+/// Converts into an array of columns.
+impl<T, const R: usize, const C: usize> From<SMatrix<T, R, C>> for [[T; R]; C] {
+    #[inline]
+    fn from(value: SMatrix<T, R, C>) -> Self {
+        value.0.map(SVector::into_array)
+    }
+}
+
+macro_rules! impl_named {
+    ($($Mat:ident $rows:tt $cols:tt ($($get:ident),+)),+) => {
+        $(
+            impl<T> From<$Mat<T>> for SMatrix<T, $rows, $cols> {
+                #[inline]
+                fn from(value: $Mat<T>) -> Self {
+                    Self([$(value.$get.into()),+])
+                }
+            }
+            impl<T> From<SMatrix<T, $rows, $cols>> for $Mat<T> {
+                #[inline]
+                fn from(value: SMatrix<T, $rows, $cols>) -> Self {
+                    let [$($get),+] = value.0;
+                    Self::from_cols($($get.into()),+)
+                }
+            }
+        )+
+    };
+}
+
+// NOTE: `MatCxR` has `C` columns and `R` rows, while `SMatrix` takes the rows first.
+impl_named!(
+    Mat2 2 2 (x, y), Mat2x3 3 2 (x, y), Mat2x4 4 2 (x, y),
+    Mat3x2 2 3 (x, y, z), Mat3 3 3 (x, y, z), Mat3x4 4 3 (x, y, z),
+    Mat4x2 2 4 (x, y, z, w), Mat4x3 3 4 (x, y, z, w), Mat4 4 4 (x, y, z, w)
+);
+
+#[cfg(test)]
+mod smat_test {
+    use super::*;
+    use crate::vec::*;
+
+    #[test]
+    fn smatrix_mul_rectangular() {
+        // 2 rows, 3 columns.
+        let a = SMatrix::from([[1, 4], [2, 5], [3, 6]]);
+        // 3 rows, 2 columns.
+        let b = SMatrix::from([[7, 9, 11], [8, 10, 12]]);
+        assert_eq!(a * b, SMatrix::from([[58, 139], [64, 154]]));
+        assert_eq!(a * SVector::new([1, 0, -1]), SVector::new([-2, -2]));
+        assert_eq!(SVector::new([1, 1]) * a, SVector::new([5, 7, 9]));
+        assert_eq!(a.transpose(), SMatrix::from([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(a * SMatrix::<i32, 3, 3>::IDENTITY, a);
+        assert_eq!(
+            SMatrix::<i32, 2, 3>::IDENTITY,
+            SMatrix::from([[1, 0], [0, 1], [0, 0]])
+        );
+    }
+
+    #[test]
+    fn smatrix_ops() {
+        let a = SMatrix::from([[1, 2], [3, 4]]);
+        assert_eq!(a + a, a * 2);
+        assert_eq!(-a - 1, SMatrix::from([[-2, -3], [-4, -5]]));
+        assert_eq!(a.mul_elementwise(a), SMatrix::from([[1, 4], [9, 16]]));
+        assert_eq!(a.as_f32(), SMatrix::from([[1.0, 2.0], [3.0, 4.0]]));
+        assert_eq!(SMatrix::<i64, 2, 2>::from_lossless(a), a.as_i64());
+        assert_eq!(a[1], SVector::new([3, 4]));
+
+        let mut b = a;
+        b *= SMatrix::IDENTITY;
+        b += a;
+        assert_eq!(b, a * 2);
+        b -= 1;
+        assert_eq!(b, a * 2 - 1);
+
+        let mut c = SMatrix::from([[1, 2, 3], [4, 5, 6]]);
+        c *= 2;
+        assert_eq!(c, SMatrix::from([[1, 2, 3], [4, 5, 6]]) * 2);
+    }
+
+    #[test]
+    fn smatrix_ref_and_scalar_ops() {
+        let a = SMatrix::from([[1, 2], [3, 4]]);
+        let (ra, rb, three, twelve) = (&a, &a, &3, &12);
+        assert_eq!(ra + rb, a + a);
+        assert_eq!(a - ra, SMatrix::ZERO);
+        assert_eq!(ra * 3, a * 3);
+        assert_eq!(ra % three, a % 3);
+        assert_eq!(2 * a, a * 2);
+        assert_eq!(twelve / ra, SMatrix::from([[12, 6], [4, 3]]));
+    }
+
+    #[test]
+    fn smatrix_matches_named() {
+        let m = Mat3::new(1, 2, 3, 4, 5, 6, 7, 8, 10);
+        let s = SMatrix::from(m);
+        let v = Vec3::new(1, -1, 2);
+        assert_eq!(Vec3::from(s * SVector::from(v)), m * v);
+        assert_eq!(Mat3::from(s * s), m * m);
+        assert_eq!(Mat3::from(s.transpose()), m.transpose());
+        assert_eq!(
+            Mat4::from(SMatrix::from(Mat4::<i32>::IDENTITY)),
+            Mat4::IDENTITY
+        );
+    }
+}
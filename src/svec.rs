@@ -0,0 +1,443 @@
+use crate::num::prelude::*;
+use crate::vec::*;
+use core::array;
+use core::ops::*;
+
+/// Builds an array from the elements of `a` and `b` combined pairwise with `f`.
+#[inline]
+pub(crate) fn zip_map<A, B, R, const N: usize>(
+    a: [A; N],
+    b: [B; N],
+    mut f: impl FnMut(A, B) -> R,
+) -> [R; N] {
+    let mut b = b.into_iter();
+    // NOTE: Both arrays have `N` elements, so `b` can't run out before `a`.
+    a.map(|a| f(a, b.next().unwrap()))
+}
+
+/// Implements a single operation for a [`SVector`].
+macro_rules! impl_op {
+    (impl $Op:ident for SVector { $op:ident }) => {
+        // NOTE: Reminder that scalars T: Copy also implement Into<SVector<T, N>>.
+        impl<V, T, const N: usize> $Op<V> for SVector<T, N>
+        where
+            V: Into<SVector<T, N>>,
+            T: $Op<T, Output = T>,
+        {
+            type Output = SVector<T, N>;
+
+            #[inline]
+            fn $op(self, rhs: V) -> Self::Output {
+                SVector(zip_map(self.0, rhs.into().0, T::$op))
+            }
+        }
+
+        impl_array_ref_op!(impl $Op for SVector [N] { $op });
+    };
+}
+
+/// Implements a single assign operation for a [`SVector`].
+macro_rules! impl_assign_op {
+    (impl $Op:ident for SVector { $op:ident }) => {
+        // NOTE: Reminder that scalars T: Copy also implement Into<SVector<T, N>>.
+        impl<V, T, const N: usize> $Op<V> for SVector<T, N>
+        where
+            V: Into<SVector<T, N>>,
+            T: $Op<T>,
+        {
+            #[inline]
+            fn $op(&mut self, rhs: V) {
+                for (a, b) in self.0.iter_mut().zip(rhs.into().0) {
+                    a.$op(b);
+                }
+            }
+        }
+    };
+}
+
+/// A vector with `N` elements, for sizes not covered by [`Vec2`], [`Vec3`] and [`Vec4`].
+///
+/// It shares the operators and the `num` traits of the named vectors and converts to and from
+/// them with `From`.
+#[derive(Debug, Clone, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct SVector<T, const N: usize>(pub [T; N]);
+
+impl<T: Copy, const N: usize> Copy for SVector<T, N> {}
+
+impl<T: Eq, const N: usize> Eq for SVector<T, N> {}
+
+impl<T: Default, const N: usize> Default for SVector<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self(array::from_fn(|_| T::default()))
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable, const N: usize> bytemuck::Zeroable for SVector<T, N> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod, const N: usize> bytemuck::Pod for SVector<T, N> {}
+
+impl<T: Zero, const N: usize> SVector<T, N> {
+    pub const ZERO: Self = Self([T::ZERO; N]);
+}
+impl<T: One, const N: usize> SVector<T, N> {
+    pub const ONE: Self = Self([T::ONE; N]);
+}
+impl<T: NegOne, const N: usize> SVector<T, N> {
+    pub const NEG_ONE: Self = Self([T::NEG_ONE; N]);
+}
+
+impl<T, const N: usize> SVector<T, N> {
+    /// Constant representing the number of elements for this vector type.
+    pub const ELEM_COUNT: usize = N;
+
+    #[inline]
+    pub const fn new(elements: [T; N]) -> Self {
+        Self(elements)
+    }
+    #[inline]
+    pub const fn splat(v: T) -> Self
+    where
+        T: Copy,
+    {
+        Self([v; N])
+    }
+
+    /// Converts this into a fixed-size array.
+    pub fn into_array(self) -> [T; N] {
+        self.0
+    }
+
+    /// View this vector as an immutable slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// View this vector as a mutable slice.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+
+    /// Returns a member wise-converted copy of this vector, using the given conversion closure.
+    #[inline]
+    pub fn map<D, F>(self, f: F) -> SVector<D, N>
+    where
+        F: FnMut(T) -> D,
+    {
+        SVector(self.0.map(f))
+    }
+
+    /// Applies the function f to each element of this vector, in-place.
+    #[inline]
+    pub fn apply<F>(&mut self, mut f: F)
+    where
+        T: Copy,
+        F: FnMut(T) -> T,
+    {
+        for v in &mut self.0 {
+            *v = f(*v);
+        }
+    }
+
+    /// Returns the sum of all elements of `self`, or zero if `N` is zero.
+    #[inline]
+    pub fn element_sum(self) -> T
+    where
+        T: Zero + Add<Output = T>,
+    {
+        self.0.into_iter().fold(T::ZERO, T::add)
+    }
+    /// Returns the product of all elements of `self`, or one if `N` is zero.
+    #[inline]
+    pub fn element_product(self) -> T
+    where
+        T: One + Mul<Output = T>,
+    {
+        self.0.into_iter().fold(T::ONE, T::mul)
+    }
+
+    /// Computes the dot product of `self` and `rhs`.
+    #[inline]
+    pub fn dot(self, rhs: Self) -> T
+    where
+        T: Zero + Add<Output = T> + Mul<Output = T>,
+    {
+        zip_map(self.0, rhs.0, T::mul)
+            .into_iter()
+            .fold(T::ZERO, T::add)
+    }
+    /// The squared length of a vector in its spatial length.
+    /// It is slightly cheaper to compute then `length` because it avoids a square root.
+    #[inline]
+    pub fn length_squared(self) -> T
+    where
+        T: Zero + Add<Output = T> + Mul<Output = T> + Copy,
+    {
+        self.dot(self)
+    }
+    /// Computes the squared Euclidean distance between `self` and `rhs`.
+    #[inline]
+    pub fn distance_squared(self, rhs: Self) -> T
+    where
+        T: Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy,
+    {
+        (self - rhs).length_squared()
+    }
+
+    /// Performs a linear interpolation between `self` and `rhs` based on the value `s`.
+    ///
+    /// When `s` is `0`, the result will be equal to `self`. When `s` is `1`, the result will be
+    /// equal to `rhs`. When `s` is outside of the range `[0, 1]`, the result is linearly
+    /// extrapolated.
+    #[inline]
+    #[must_use]
+    pub fn lerp(self, rhs: Self, s: T) -> Self
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy,
+    {
+        self + (rhs - self) * s
+    }
+}
+
+impl<T: Compare + Copy, const N: usize> SVector<T, N> {
+    /// Returns a vector containing the minimum values for each element of `self` and `rhs`.
+    #[inline]
+    pub fn min(self, rhs: Self) -> Self {
+        Self(zip_map(self.0, rhs.0, |a, b| T::select(a.cmplt(b), a, b)))
+    }
+    /// Returns a vector containing the maximum values for each element of `self` and `rhs`.
+    #[inline]
+    pub fn max(self, rhs: Self) -> Self {
+        Self(zip_map(self.0, rhs.0, |a, b| T::select(a.cmpgt(b), a, b)))
+    }
+    /// Component-wise clamping of values similar to [`f32::clamp`].
+    ///
+    /// Each element in `min` must be less-or-equal to the corresponding element in `max`.
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.min(max).max(min)
+    }
+}
+
+impl<T, const N: usize> SVector<T, N>
+where
+    T: Float + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Copy,
+{
+    #[inline]
+    #[must_use]
+    pub fn length(self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        self / self.length()
+    }
+
+    /// Computes the Euclidean distance between `self` and `rhs`.
+    #[inline]
+    pub fn distance(self, rhs: Self) -> T {
+        (self - rhs).length()
+    }
+}
+
+impl<T, const N: usize> SVector<T, N> {
+    impl_cast!(@as SVector [N]
+        as_usize usize, as_u8 u8, as_u16 u16, as_u32 u32, as_u64 u64, as_u128 u128,
+        as_isize isize, as_i8 i8, as_i16 i16, as_i32 i32, as_i64 i64, as_i128 i128,
+        as_f32 f32, as_f64 f64
+    );
+    impl_cast!(@lossless SVector [N]);
+}
+
+impl<T, U, const N: usize> CheckedCast<SVector<U, N>> for SVector<T, N>
+where
+    T: CheckedCast<U>,
+{
+    #[inline]
+    fn checked_cast(self) -> Option<SVector<U, N>> {
+        let out = self.0.map(T::checked_cast);
+        if out.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(SVector(out.map(Option::unwrap)))
+    }
+}
+
+impl_op!(impl Add for SVector { add });
+impl_op!(impl Sub for SVector { sub });
+impl_op!(impl Mul for SVector { mul });
+impl_op!(impl Div for SVector { div });
+impl_op!(impl Rem for SVector { rem });
+impl_op!(impl BitAnd for SVector { bitand });
+impl_op!(impl BitOr for SVector { bitor });
+impl_op!(impl BitXor for SVector { bitxor });
+impl_op!(impl Shl for SVector { shl });
+impl_op!(impl Shr for SVector { shr });
+impl_assign_op!(impl AddAssign for SVector { add_assign });
+impl_assign_op!(impl SubAssign for SVector { sub_assign });
+impl_assign_op!(impl MulAssign for SVector { mul_assign });
+impl_assign_op!(impl DivAssign for SVector { div_assign });
+impl_assign_op!(impl RemAssign for SVector { rem_assign });
+impl_assign_op!(impl BitAndAssign for SVector { bitand_assign });
+impl_assign_op!(impl BitOrAssign for SVector { bitor_assign });
+impl_assign_op!(impl BitXorAssign for SVector { bitxor_assign });
+impl_assign_op!(impl ShlAssign for SVector { shl_assign });
+impl_assign_op!(impl ShrAssign for SVector { shr_assign });
+
+impl<T: Neg<Output = T>, const N: usize> Neg for SVector<T, N> {
+    type Output = SVector<T, N>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.map(T::neg)
+    }
+}
+
+impl<T: Not<Output = T>, const N: usize> Not for SVector<T, N> {
+    type Output = SVector<T, N>;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        self.map(T::not)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for SVector<T, N> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+impl<T, const N: usize> IndexMut<usize> for SVector<T, N> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T, const N: usize> Deref for SVector<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+impl<T, const N: usize> DerefMut for SVector<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T: Copy, const N: usize> From<T> for SVector<T, N> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::splat(value)
+    }
+}
+impl<T, const N: usize> From<[T; N]> for SVector<T, N> {
+    #[inline]
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+impl<T, const N: usize> From<SVector<T, N>> for [T; N] {
+    #[inline]
+    fn from(value: SVector<T, N>) -> Self {
+        value.0
+    }
+}
+
+macro_rules! impl_named {
+    ($($Vec:ident $size:tt ($($get:ident),+)),+) => {
+        $(
+            impl<T> From<$Vec<T>> for SVector<T, $size> {
+                #[inline]
+                fn from(value: $Vec<T>) -> Self {
+                    Self(value.into_array())
+                }
+            }
+            impl<T> From<SVector<T, $size>> for $Vec<T> {
+                #[inline]
+                fn from(value: SVector<T, $size>) -> Self {
+                    let [$($get),+] = value.0;
+                    Self::new($($get),+)
+                }
+            }
+        )+
+    };
+}
+
+impl_named!(Vec2 2 (x, y), Vec3 3 (x, y, z), Vec4 4 (x, y, z, w));
+
+impl_array_prim!(SVector [N], usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64
+    => (Add add, Sub sub, Mul mul, Div div, Rem rem));
+impl_array_prim!(SVector [N], usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128
+    => (BitAnd bitand, BitOr bitor, BitXor bitxor, Shl shl, Shr shr));
+
+#[cfg(test)]
+mod svec_test {
+    use super::*;
+
+    #[test]
+    fn svector_ops() {
+        let a = SVector::new([1, 2, 3, 4, 5]);
+        let b = SVector::new([5, 4, 3, 2, 1]);
+        assert_eq!(a + b, SVector::splat(6));
+        assert_eq!(a - 1, SVector::new([0, 1, 2, 3, 4]));
+        assert_eq!(2 * a, SVector::new([2, 4, 6, 8, 10]));
+        let (ra, rb) = (&a, &b);
+        assert_eq!(ra * rb, SVector::new([5, 8, 9, 8, 5]));
+        assert_eq!(-a, SVector::new([-1, -2, -3, -4, -5]));
+        assert_eq!(a & 1, SVector::new([1, 0, 1, 0, 1]));
+        assert_eq!(a.dot(b), 35);
+        assert_eq!(a.element_sum(), 15);
+        assert_eq!(a.element_product(), 120);
+        assert_eq!(a.min(b), SVector::new([1, 2, 3, 2, 1]));
+        assert_eq!(a[4], 5);
+
+        let mut c = a;
+        c += b;
+        c <<= 1;
+        assert_eq!(c, SVector::splat(12));
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn svector_float_and_casts() {
+        let a = SVector::new([3.0, 0.0, 4.0, 0.0, 0.0, 0.0]);
+        assert_eq!(a.length(), 5.0);
+        assert_eq!(a.normalize(), SVector::new([0.6, 0.0, 0.8, 0.0, 0.0, 0.0]));
+        assert_eq!(a.as_i32(), SVector::new([3, 0, 4, 0, 0, 0]));
+        assert_eq!(
+            CheckedCast::<SVector<u8, 2>>::checked_cast(SVector::new([1.5_f32, 300.0])),
+            None
+        );
+        assert_eq!(
+            CheckedCast::<SVector<u8, 2>>::checked_cast(SVector::new([1.5_f32, 3.0])),
+            Some(SVector::new([1, 3]))
+        );
+    }
+
+    #[test]
+    fn svector_named_conversions() {
+        let v = Vec3::new(1, 2, 3);
+        let s = SVector::from(v);
+        assert_eq!(s, SVector::new([1, 2, 3]));
+        assert_eq!(Vec3::from(s), v);
+        assert_eq!(
+            Vec4::from(SVector::new([1, 2, 3, 4])),
+            Vec4::new(1, 2, 3, 4)
+        );
+        assert_eq!(SVector::<i32, 2>::ZERO, SVector::from(Vec2::ZERO));
+    }
+}
@@ -1,18 +1,12 @@
+use crate::bvec::*;
+use crate::kernel::VecKernel;
 use crate::num::prelude::*;
 use core::borrow::*;
 use core::iter::*;
+use core::mem::{align_of, offset_of, size_of};
 use core::ops::*;
 use core::slice;
 
-macro_rules! reduce_fn {
-    ($fn:expr, $a:expr, $b:expr) => { $fn($a, $b) };
-    ($fn:expr, $a:expr, $b:expr, $($v:expr),+) => { reduce_fn!($fn, reduce_fn!($fn, $a, $b), $($v),+) };
-}
-macro_rules! reduce_op {
-    ($op:tt, $a:expr, $b:expr) => { $a $op $b };
-    ($op:tt, $a:expr, $b:expr, $($v:expr),+) => { reduce_op!($op, reduce_op!($op, $a, $b), $($v),+) };
-}
-
 /// Implements a single operation for a vector.
 macro_rules! impl_op {
     (impl $Op:ident for $Vec:ident { $op:ident } ($($get:tt),+)) => {
@@ -26,6 +20,22 @@ macro_rules! impl_op {
                 $Vec::new($(self.$get.$op(rhs.$get)),+)
             }
         }
+        impl_op!(@ref impl $Op for $Vec { $op } ($($get),+));
+    };
+    // Same as above, but the owned operation goes through `VecKernel` so it can have a SIMD fast path.
+    (impl $Op:ident for $Vec:ident { $op:ident } ($($get:tt),+) kernel) => {
+        // NOTE: Reminder that scalars T: Copy also implement Into<$Vec<T>>.
+        impl<V, T> $Op<V> for $Vec<T> where V: Into<$Vec<T>>, T: $Op<T, Output = T> {
+            type Output = $Vec<T>;
+
+            #[inline]
+            fn $op(self, rhs: V) -> Self::Output {
+                <$Vec<T> as VecKernel<T>>::$op(self, rhs.into())
+            }
+        }
+        impl_op!(@ref impl $Op for $Vec { $op } ($($get),+));
+    };
+    (@ref impl $Op:ident for $Vec:ident { $op:ident } ($($get:tt),+)) => {
         impl<'a, T> $Op<&'a $Vec<T>> for $Vec<T> where T: $Op<&'a T, Output = T> {
             type Output = $Vec<T>;
 
@@ -84,22 +94,64 @@ macro_rules! impl_assign_op {
     };
 }
 
+/// Implements component-wise versions of the [`Integer`] methods for a vector.
+macro_rules! impl_integer_ops {
+    ($gets:tt $($op:ident)+) => {
+        $(impl_integer_ops!(@one $gets $op);)+
+    };
+    (@checked $gets:tt $($op:ident)+) => {
+        $(impl_integer_ops!(@checked_one $gets $op);)+
+    };
+    (@one ($($get:tt),+) $op:ident) => {
+        #[doc = concat!("Applies `", stringify!($op), "` to each element.")]
+        #[inline]
+        #[must_use]
+        pub fn $op(self, rhs: Self) -> Self {
+            Self { $($get: self.$get.$op(rhs.$get)),+ }
+        }
+    };
+    (@checked_one ($($get:tt),+) $op:ident) => {
+        #[doc = concat!("Applies `", stringify!($op), "` to each element, returns `None` if any of them fails.")]
+        #[inline]
+        #[must_use]
+        pub fn $op(self, rhs: Self) -> Option<Self> {
+            Some(Self { $($get: self.$get.$op(rhs.$get)?),+ })
+        }
+    };
+}
+
 macro_rules! impl_vec {
-    ($Vec:ident, $size:tt, ($($get:tt),+), ($($index:tt),+), $tuple:tt) => {
+    ($Vec:ident, $BVec:ident, $size:tt, ($($get:tt),+), ($($index:tt),+), $tuple:tt) => {
+        // NOTE: `#[repr(C)]` guarantees the same layout as `[T; $size]`, which `as_slice` and the
+        // `bytemuck` impls rely on.
         #[derive(Debug, Default, Clone, Hash, PartialEq)]
+        #[repr(C)]
         pub struct $Vec<T> {
             $(pub $get: T),+
         }
 
-        impl<T: Copy> Copy for $Vec<T> {}
-
-        impl<T: Eq> Eq for $Vec<T> {}
+        const _: () = {
+            assert!(size_of::<$Vec<u8>>() == $size * size_of::<u8>());
+            assert!(size_of::<$Vec<f32>>() == $size * size_of::<f32>());
+            assert!(size_of::<$Vec<f64>>() == $size * size_of::<f64>());
+            assert!(size_of::<$Vec<u128>>() == $size * size_of::<u128>());
+            assert!(align_of::<$Vec<f32>>() == align_of::<f32>());
+            assert!(align_of::<$Vec<f64>>() == align_of::<f64>());
+            $(assert!(offset_of!($Vec<f32>, $get) == $index * size_of::<f32>());)+
+        };
 
         #[cfg(feature = "bytemuck")]
         unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for $Vec<T> {}
         #[cfg(feature = "bytemuck")]
         unsafe impl<T: bytemuck::Pod> bytemuck::Pod for $Vec<T> {}
 
+        impl_vec!(@common $Vec, $BVec, $size, ($($get),+), ($($index),+), $tuple);
+    };
+    (@common $Vec:ident, $BVec:ident, $size:tt, ($($get:tt),+), ($($index:tt),+), $tuple:tt) => {
+        impl<T: Copy> Copy for $Vec<T> {}
+
+        impl<T: Eq> Eq for $Vec<T> {}
+
         impl<T: Zero> $Vec<T> {
             pub const ZERO: Self = Self { $($get: T::ZERO),+ };
         }
@@ -114,16 +166,61 @@ macro_rules! impl_vec {
             pub const ELEM_COUNT:usize = $size;
         }
 
-        impl_op!(impl Add for $Vec { add } ($($get),+));
-        impl_op!(impl Sub for $Vec { sub } ($($get),+));
-        impl_op!(impl Mul for $Vec { mul } ($($get),+));
-        impl_op!(impl Div for $Vec { div } ($($get),+));
+        impl_cast!($Vec ($($get),+));
+
+        impl_op!(impl Add for $Vec { add } ($($get),+) kernel);
+        impl_op!(impl Sub for $Vec { sub } ($($get),+) kernel);
+        impl_op!(impl Mul for $Vec { mul } ($($get),+) kernel);
+        impl_op!(impl Div for $Vec { div } ($($get),+) kernel);
         impl_op!(impl Rem for $Vec { rem } ($($get),+));
+        impl_op!(impl BitAnd for $Vec { bitand } ($($get),+));
+        impl_op!(impl BitOr for $Vec { bitor } ($($get),+));
+        impl_op!(impl BitXor for $Vec { bitxor } ($($get),+));
+        impl_op!(impl Shl for $Vec { shl } ($($get),+));
+        impl_op!(impl Shr for $Vec { shr } ($($get),+));
         impl_assign_op!(impl AddAssign for $Vec { add_assign } ($($get),+));
         impl_assign_op!(impl SubAssign for $Vec { sub_assign } ($($get),+));
         impl_assign_op!(impl MulAssign for $Vec { mul_assign } ($($get),+));
         impl_assign_op!(impl DivAssign for $Vec { div_assign } ($($get),+));
         impl_assign_op!(impl RemAssign for $Vec { rem_assign } ($($get),+));
+        impl_assign_op!(impl BitAndAssign for $Vec { bitand_assign } ($($get),+));
+        impl_assign_op!(impl BitOrAssign for $Vec { bitor_assign } ($($get),+));
+        impl_assign_op!(impl BitXorAssign for $Vec { bitxor_assign } ($($get),+));
+        impl_assign_op!(impl ShlAssign for $Vec { shl_assign } ($($get),+));
+        impl_assign_op!(impl ShrAssign for $Vec { shr_assign } ($($get),+));
+
+        impl<T> VecKernel<T> for $Vec<T> {
+            specializable! {
+                #[inline]
+                fn add(self, rhs: Self) -> Self where T: Add<Output = T> {
+                    Self { $($get: self.$get + rhs.$get),+ }
+                }
+            }
+            specializable! {
+                #[inline]
+                fn sub(self, rhs: Self) -> Self where T: Sub<Output = T> {
+                    Self { $($get: self.$get - rhs.$get),+ }
+                }
+            }
+            specializable! {
+                #[inline]
+                fn mul(self, rhs: Self) -> Self where T: Mul<Output = T> {
+                    Self { $($get: self.$get * rhs.$get),+ }
+                }
+            }
+            specializable! {
+                #[inline]
+                fn div(self, rhs: Self) -> Self where T: Div<Output = T> {
+                    Self { $($get: self.$get / rhs.$get),+ }
+                }
+            }
+            specializable! {
+                #[inline]
+                fn dot(self, rhs: Self) -> T where T: Add<Output = T> + Mul<Output = T> {
+                    reduce_op!(+, $(self.$get * rhs.$get),+)
+                }
+            }
+        }
 
         impl<T> AsRef<[T]> for $Vec<T> {
             #[inline]
@@ -239,6 +336,7 @@ macro_rules! impl_vec {
             /// View this vector as an immutable slice.
             #[inline]
             pub fn as_slice(&self) -> &[T] {
+                // SAFETY: `$Vec<T>` is `#[repr(C)]` and only contains `$size` fields of type `T`.
                 unsafe {
                     slice::from_raw_parts(self.as_ptr_priv(), $size)
                 }
@@ -247,6 +345,7 @@ macro_rules! impl_vec {
             /// View this vector as a mutable slice.
             #[inline]
             pub fn as_mut_slice(&mut self) -> &mut [T] {
+                // SAFETY: `$Vec<T>` is `#[repr(C)]` and only contains `$size` fields of type `T`.
                 unsafe {
                     slice::from_raw_parts_mut(self.as_mut_ptr_priv(), $size)
                 }
@@ -297,7 +396,7 @@ macro_rules! impl_vec {
             /// Computes the dot product of `self` and `rhs`.
             #[inline]
             pub fn dot(self, rhs: Self) -> T where T: Add<Output = T> + Mul<Output = T> {
-                (self * rhs).element_sum()
+                <Self as VecKernel<T>>::dot(self, rhs)
             }
             /// The squared length of a vector in its spatial length.
             /// It is slightly cheaper to compute then `length` because it avoids a square root.
@@ -305,22 +404,104 @@ macro_rules! impl_vec {
             pub fn length_squared(self) -> T where T: Add<Output = T> + Mul<Output = T> + Copy {
                 self.dot(self)
             }
+            /// Computes the squared Euclidean distance between `self` and `rhs`.
+            #[inline]
+            pub fn distance_squared(self, rhs: Self) -> T
+                where T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy
+            {
+                (self - rhs).length_squared()
+            }
+
+            /// Performs a linear interpolation between `self` and `rhs` based on the value `s`.
+            ///
+            /// When `s` is `0`, the result will be equal to `self`. When `s` is `1`, the result
+            /// will be equal to `rhs`. When `s` is outside of the range `[0, 1]`, the result is
+            /// linearly extrapolated.
+            #[inline]
+            #[must_use]
+            pub fn lerp(self, rhs: Self, s: T) -> Self
+                where T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy
+            {
+                self + (rhs - self) * s
+            }
+
+            /// Returns the vector projection of `self` onto `rhs`.
+            ///
+            /// `rhs` must be of non-zero length.
+            #[inline]
+            #[must_use]
+            pub fn project_onto(self, rhs: Self) -> Self
+                where T: Add<Output = T> + Mul<Output = T> + Div<Output = T> + Copy
+            {
+                rhs * (self.dot(rhs) / rhs.length_squared())
+            }
+            /// Returns the vector rejection of `self` from `rhs`, the part of `self` that is
+            /// perpendicular to `rhs`.
+            ///
+            /// `rhs` must be of non-zero length.
+            #[inline]
+            #[must_use]
+            pub fn reject_from(self, rhs: Self) -> Self
+                where T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Copy
+            {
+                self - self.project_onto(rhs)
+            }
         }
 
-        impl<T: PartialOrd> $Vec<T> {
+        impl<T: Compare> $Vec<T> {
+            /// Returns a mask with the result of `self < rhs` for each element.
+            #[inline]
+            pub fn cmplt(self, rhs: Self) -> $BVec<T::Mask> {
+                $BVec { $($get: self.$get.cmplt(rhs.$get)),+ }
+            }
+            /// Returns a mask with the result of `self <= rhs` for each element.
+            #[inline]
+            pub fn cmple(self, rhs: Self) -> $BVec<T::Mask> {
+                $BVec { $($get: self.$get.cmple(rhs.$get)),+ }
+            }
+            /// Returns a mask with the result of `self > rhs` for each element.
+            #[inline]
+            pub fn cmpgt(self, rhs: Self) -> $BVec<T::Mask> {
+                $BVec { $($get: self.$get.cmpgt(rhs.$get)),+ }
+            }
+            /// Returns a mask with the result of `self >= rhs` for each element.
+            #[inline]
+            pub fn cmpge(self, rhs: Self) -> $BVec<T::Mask> {
+                $BVec { $($get: self.$get.cmpge(rhs.$get)),+ }
+            }
+            /// Returns a mask with the result of `self == rhs` for each element.
+            #[inline]
+            pub fn cmpeq(self, rhs: Self) -> $BVec<T::Mask> {
+                $BVec { $($get: self.$get.cmpeq(rhs.$get)),+ }
+            }
+            /// Returns a mask with the result of `self != rhs` for each element.
+            #[inline]
+            pub fn cmpne(self, rhs: Self) -> $BVec<T::Mask> {
+                $BVec { $($get: self.$get.cmpne(rhs.$get)),+ }
+            }
+
+            /// Returns a vector with the elements of `if_true` where `mask` is set and the
+            /// elements of `if_false` everywhere else.
+            #[inline]
+            pub fn select(mask: $BVec<T::Mask>, if_true: Self, if_false: Self) -> Self {
+                Self { $($get: T::select(mask.$get, if_true.$get, if_false.$get)),+ }
+            }
+        }
+
+        impl<T: Compare + Copy> $Vec<T> {
             /// Returns a vector containing the minimum values for each element of `self` and `rhs`.
             ///
             /// In other words, this computes `[min(self.x, rhs.x), min(self.y, rhs.y), ...]`.
             #[inline]
             pub fn min(self, rhs: Self) -> Self {
-                Self { $($get: if self.$get < rhs.$get {self.$get} else {rhs.$get}),+ }
+                Self { $($get: T::select(self.$get.cmplt(rhs.$get), self.$get, rhs.$get)),+ }
             }
             /// Returns a vector containing the maximum values for each element of `self` and `rhs`.
             ///
             /// In other words, this computes `[max(self.x, rhs.x), max(self.y, rhs.y), ...]`.
             #[inline]
             pub fn max(self, rhs: Self) -> Self {
-                Self { $($get: if self.$get > rhs.$get {self.$get} else {rhs.$get}),+ }
+                Self { $($get: T::select(self.$get.cmpgt(rhs.$get), self.$get, rhs.$get)),+ }
             }
             /// Component-wise clamping of values similar to [`f32::clamp`].
             ///
@@ -335,7 +516,7 @@ macro_rules! impl_vec {
             /// In other words, this computes `min(x, y, ...)`.
             #[inline]
             pub fn min_element(self) -> T {
-                let min = |a, b| if a < b { a } else { b };
+                let min = |a: T, b: T| T::select(a.cmplt(b), a, b);
                 reduce_fn!(min, $(self.$get),+)
             }
             /// Returns the horizontal maximum of `self`.
@@ -343,7 +524,7 @@ macro_rules! impl_vec {
             /// In other words, this computes `max(x, y, ...)`.
             #[inline]
             pub fn max_element(self) -> T {
-                let max = |a, b| if a > b { a } else { b };
+                let max = |a: T, b: T| T::select(a.cmpgt(b), a, b);
                 reduce_fn!(max, $(self.$get),+)
             }
 
@@ -365,7 +546,87 @@ macro_rules! impl_vec {
                 self / self.length()
             }
 
+            /// Computes the Euclidean distance between `self` and `rhs`.
+            #[inline]
+            pub fn distance(self, rhs: Self) -> T
+                where T: Sub<Output = T>
+            {
+                (self - rhs).length()
+            }
+        }
+
+        impl<T> $Vec<T>
+            where T: Float + Compare + Zero + One + Epsilon + Infinity + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T> + Copy
+        {
+            /// Returns the angle in radians between `self` and `rhs`, in the range `[0, pi]`.
+            ///
+            /// Both vectors must be of non-zero length.
+            #[inline]
+            pub fn angle_between(self, rhs: Self) -> T {
+                let cos = self.dot(rhs) / (self.length_squared() * rhs.length_squared()).sqrt();
+                // NOTE: Rounding can push the cosine of (anti)parallel vectors slightly outside [-1, 1].
+                let cos = T::select(cos.cmpgt(T::ONE), T::ONE, cos);
+                let cos = T::select(cos.cmplt(-T::ONE), -T::ONE, cos);
+                cos.acos()
+            }
+
+            /// Returns `self` normalized to length 1, or `None` if the length is zero or so close
+            /// to zero or infinity that the result would not be finite.
+            ///
+            /// For `Simd` elements the whole batch is `None` as soon as a single lane can not be
+            /// normalized. Use [`Self::normalize_or`] to fall back lane by lane instead.
+            #[inline]
+            #[must_use]
+            pub fn try_normalize(self) -> Option<Self> {
+                let length = self.length();
+                // NOTE: Comparing against infinity is false for NaN as well.
+                if T::all(length.cmplt(T::INFINITY)) && T::all((T::ONE / length).cmplt(T::INFINITY)) {
+                    Some(self / length)
+                } else {
+                    None
+                }
+            }
+            /// Returns `self` normalized to length 1, or `fallback` if that is not possible.
+            ///
+            /// See [`Self::try_normalize`].
+            #[inline]
+            #[must_use]
+            pub fn normalize_or(self, fallback: Self) -> Self {
+                let length = self.length();
+                // NOTE: Comparing against infinity is false for NaN as well.
+                let finite = length.cmplt(T::INFINITY);
+                let rcp_finite = (T::ONE / length).cmplt(T::INFINITY);
+                Self { $($get: T::select(finite, T::select(rcp_finite, self.$get / length, fallback.$get), fallback.$get)),+ }
+            }
+            /// Returns `self` normalized to length 1, or zero if that is not possible.
+            ///
+            /// See [`Self::try_normalize`].
+            #[inline]
+            #[must_use]
+            pub fn normalize_or_zero(self) -> Self {
+                self.normalize_or(Self::ZERO)
+            }
+            /// Returns whether `self` has a length of 1, within the square root of `T::EPSILON`.
+            ///
+            /// For `Simd` elements this is `true` only if every lane is normalized.
+            #[inline]
+            pub fn is_normalized(self) -> bool {
+                T::all((self.length_squared() - T::ONE).abs().cmple(T::EPSILON.sqrt()))
+            }
 
+            /// Returns `self` with its length clamped to the range `[min, max]`.
+            ///
+            /// `min` must be less-or-equal to `max` and `self` must be of non-zero length if `min`
+            /// is larger than zero.
+            #[inline]
+            #[must_use]
+            pub fn clamp_length(self, min: T, max: T) -> Self {
+                let length_squared = self.length_squared();
+                let length = length_squared.sqrt();
+                let scale = T::select(length_squared.cmpgt(max * max), max / length, T::ONE);
+                let scale = T::select(length_squared.cmplt(min * min), min / length, scale);
+                self * scale
+            }
         }
 
         impl<T: Float> $Vec<T> {
@@ -384,6 +645,27 @@ macro_rules! impl_vec {
             pub fn fract(self) -> Self {
                 Self { $($get: self.$get.fract()),+ }
             }
+
+            pub fn abs(self) -> Self {
+                Self { $($get: self.$get.abs()),+ }
+            }
+            pub fn signum(self) -> Self {
+                Self { $($get: self.$get.signum()),+ }
+            }
+            pub fn recip(self) -> Self {
+                Self { $($get: self.$get.recip()),+ }
+            }
+            /// Computes `(self * a) + b` for each element with only one rounding error.
+            pub fn mul_add(self, a: Self, b: Self) -> Self {
+                Self { $($get: self.$get.mul_add(a.$get, b.$get)),+ }
+            }
+        }
+
+        impl<T: Integer> $Vec<T> {
+            impl_integer_ops!(($($get),+) wrapping_add wrapping_sub wrapping_mul);
+            impl_integer_ops!(($($get),+) saturating_add saturating_sub saturating_mul);
+            impl_integer_ops!(@checked ($($get),+) checked_add checked_sub checked_mul checked_div checked_rem);
+            impl_integer_ops!(($($get),+) div_euclid rem_euclid);
         }
 
         impl<T: Neg<Output = T>> Neg for $Vec<T> {
@@ -395,6 +677,15 @@ macro_rules! impl_vec {
             }
         }
 
+        impl<T: Not<Output = T>> Not for $Vec<T> {
+            type Output = $Vec<T>;
+
+            #[inline]
+            fn not(self) -> Self::Output {
+                Self::Output { $($get: !self.$get),+ }
+            }
+        }
+
         impl<T> Index<usize> for $Vec<T> {
             type Output = T;
 
@@ -451,9 +742,38 @@ macro_rules! impl_vec {
     };
 }
 
-impl_vec!(Vec2, 2, (x, y), (0, 1), (T, T));
-impl_vec!(Vec3, 3, (x, y, z), (0, 1, 2), (T, T, T));
-impl_vec!(Vec4, 4, (x, y, z, w), (0, 1, 2, 3), (T, T, T, T));
+impl_vec!(Vec2, BVec2, 2, (x, y), (0, 1), (T, T));
+impl_vec!(Vec3, BVec3, 3, (x, y, z), (0, 1, 2), (T, T, T));
+impl_vec!(Vec4, BVec4, 4, (x, y, z, w), (0, 1, 2, 3), (T, T, T, T));
+
+/// A 3D vector aligned to 16 bytes, so it can be loaded into a single SIMD register.
+///
+/// Unlike [`Vec3`] this type has padding after `z`, so it does not implement `bytemuck::Pod`.
+#[cfg(feature = "simd")]
+#[derive(Debug, Default, Clone, Hash, PartialEq)]
+#[repr(C, align(16))]
+pub struct Vec3A<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+#[cfg(feature = "simd")]
+impl_vec!(@common Vec3A, BVec3, 3, (x, y, z), (0, 1, 2), (T, T, T));
+
+#[cfg(feature = "simd")]
+impl<T> From<Vec3<T>> for Vec3A<T> {
+    #[inline]
+    fn from(value: Vec3<T>) -> Self {
+        Self::new(value.x, value.y, value.z)
+    }
+}
+#[cfg(feature = "simd")]
+impl<T> From<Vec3A<T>> for Vec3<T> {
+    #[inline]
+    fn from(value: Vec3A<T>) -> Self {
+        Self::new(value.x, value.y, value.z)
+    }
+}
 
 impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Vec3<T> {
     /// Computes the cross product of `self` and `rhs`.
@@ -484,6 +804,85 @@ impl<T: Float + Zero + One + Copy + Add<Output = T> + Sub<Output = T> + Mul<Outp
     }
 }
 
+impl<T> Vec3<T>
+where
+    T: Float + Zero + Add<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T> + Copy,
+{
+    /// Returns some unit vector that is orthogonal to `self`.
+    ///
+    /// `self` must not be zero.
+    pub fn any_orthonormal_vector(self) -> Self {
+        if self.x.abs() > self.z.abs() {
+            Self::new(-self.y, self.x, T::ZERO)
+        } else {
+            Self::new(T::ZERO, -self.z, self.y)
+        }
+        .normalize()
+    }
+}
+
+impl<T> Vec2<T> {
+    /// Creates a 3D vector from the elements of `self` and `z`.
+    #[inline]
+    pub fn extend(self, z: T) -> Vec3<T> {
+        Vec3::new(self.x, self.y, z)
+    }
+}
+
+impl<T> Vec3<T> {
+    /// Creates a 4D vector from the elements of `self` and `w`.
+    #[inline]
+    pub fn extend(self, w: T) -> Vec4<T> {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+    /// Creates a 2D vector from the `x` and `y` elements of `self`, discarding `z`.
+    #[inline]
+    pub fn truncate(self) -> Vec2<T> {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// Returns `self` as a point in homogeneous coordinates, with a `w` of 1.
+    ///
+    /// Translations apply to points, see [`Mat4::transform_point3`](crate::Mat4::transform_point3).
+    #[inline]
+    pub fn to_homogeneous_point(self) -> Vec4<T>
+    where
+        T: One,
+    {
+        self.extend(T::ONE)
+    }
+    /// Returns `self` as a direction in homogeneous coordinates, with a `w` of 0.
+    ///
+    /// Translations do not apply to directions, see
+    /// [`Mat4::transform_vector3`](crate::Mat4::transform_vector3).
+    #[inline]
+    pub fn to_homogeneous_vector(self) -> Vec4<T>
+    where
+        T: Zero,
+    {
+        self.extend(T::ZERO)
+    }
+}
+
+impl<T> Vec4<T> {
+    /// Creates a 3D vector from the `x`, `y` and `z` elements of `self`, discarding `w`.
+    #[inline]
+    pub fn truncate(self) -> Vec3<T> {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// Divides `x`, `y` and `z` by `w`, also known as the perspective divide.
+    ///
+    /// This converts a point in homogeneous coordinates back into 3D.
+    #[inline]
+    pub fn xyz_div_w(self) -> Vec3<T>
+    where
+        T: Div<Output = T> + Copy,
+    {
+        Vec3::new(self.x / self.w, self.y / self.w, self.z / self.w)
+    }
+}
+
 // ====== //
 // CONSTS //
 // ====== //
@@ -519,7 +918,7 @@ impl<T: NegOne + Zero> Vec4<T> {
 }
 
 macro_rules! impl_prim_ops {
-    ($Vec:ident<$type:ident>, $get:tt, $($Op:ident $op:ident),+) => { $(impl_prim_op!($Vec<$type>, $get, $Op $op);)+ };
+    ($Vec:ident<$type:ident>, $get:tt, ($($Op:ident $op:ident),+)) => { $(impl_prim_op!($Vec<$type>, $get, $Op $op);)+ };
 }
 macro_rules! impl_prim_op {
     ($Vec:ident<$type:ident>, ($($get:tt),+), $Op:ident $op:ident) => {
@@ -559,19 +958,24 @@ macro_rules! impl_prim_op {
 }
 
 macro_rules! impl_prim {
-    ($($type:ident)+) => {
-        impl_prim!(Vec2, (x, y), $($type)+);
-        impl_prim!(Vec3, (x, y, z), $($type)+);
-        impl_prim!(Vec4, (x, y, z, w), $($type)+);
+    ($($type:ident)+ => $ops:tt) => {
+        impl_prim!(Vec2, (x, y), $ops, $($type)+);
+        impl_prim!(Vec3, (x, y, z), $ops, $($type)+);
+        impl_prim!(Vec4, (x, y, z, w), $ops, $($type)+);
+        #[cfg(feature = "simd")]
+        impl_prim!(Vec3A, (x, y, z), $ops, $($type)+);
     };
-    ($Vec:ident, $get:tt, $($type:ident)+) => {
+    ($Vec:ident, $get:tt, $ops:tt, $($type:ident)+) => {
         $(
-            impl_prim_ops!($Vec<$type>, $get, Add add, Sub sub, Mul mul, Div div, Rem rem);
+            impl_prim_ops!($Vec<$type>, $get, $ops);
         )+
     };
 }
 
-impl_prim!(usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+impl_prim!(usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64
+    => (Add add, Sub sub, Mul mul, Div div, Rem rem));
+impl_prim!(usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128
+    => (BitAnd bitand, BitOr bitor, BitXor bitxor, Shl shl, Shr shr));
 
 #[macro_export]
 macro_rules! swizzle {
@@ -635,3 +1039,246 @@ mod swizzle_test {
         assert_eq!(result, Vec2::new(1, 2))
     }
 }
+
+#[cfg(test)]
+mod layout_test {
+    use super::*;
+
+    #[test]
+    fn vec_as_slice() {
+        let mut v = Vec3::new(1, 2, 3);
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        v.as_mut_slice()[2] = 4;
+        assert_eq!(v.z, 4);
+    }
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn vec_cast_slice() {
+        let v = [Vec3::new(1.0_f32, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)];
+        let floats: &[f32] = bytemuck::cast_slice(&v);
+        assert_eq!(floats, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+}
+
+#[cfg(test)]
+mod vec_test {
+    use super::*;
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn assert_approx_eq(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-9, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn vec_lerp_distance() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(3.0, 2.0, -1.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Vec3::new(2.0, 2.0, 1.0));
+        assert_eq!(a.distance_squared(b), 20.0);
+        assert_eq!(Vec2::new(1.0, 1.0).distance(Vec2::new(4.0, 5.0)), 5.0);
+        assert_eq!(Vec2::new(1, 1).distance_squared(Vec2::new(4, 5)), 25);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn vec_angle_project_reject() {
+        use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+        assert_eq!(
+            Vec2::new(1.0, 0.0).angle_between(Vec2::new(0.0, 2.0)),
+            FRAC_PI_2
+        );
+        assert_approx_eq(
+            &[Vec2::new(1.0, 0.0).angle_between(Vec2::new(1.0, 1.0))],
+            &[FRAC_PI_4],
+        );
+        assert_eq!(
+            Vec3::new(1.0, 1.0, 1.0).angle_between(Vec3::new(2.0, 2.0, 2.0)),
+            0.0
+        );
+        assert_eq!(
+            Vec3::new(1.0, 1.0, 1.0).angle_between(Vec3::new(-1.0, -1.0, -1.0)),
+            PI
+        );
+
+        let a = Vec3::new(2.0, 3.0, 4.0);
+        let b = Vec3::new(0.0, 2.0, 0.0);
+        assert_eq!(a.project_onto(b), Vec3::new(0.0, 3.0, 0.0));
+        assert_eq!(a.reject_from(b), Vec3::new(2.0, 0.0, 4.0));
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn vec_normalize_variants() {
+        let v = Vec3::new(3.0, 0.0, 4.0);
+        assert_eq!(v.try_normalize(), Some(Vec3::new(0.6, 0.0, 0.8)));
+        assert!(v.normalize().is_normalized());
+        assert!(!v.is_normalized());
+
+        assert_eq!(Vec3::<f64>::ZERO.try_normalize(), None);
+        assert_eq!(Vec3::splat(f64::INFINITY).try_normalize(), None);
+        assert_eq!(Vec3::splat(f64::NAN).try_normalize(), None);
+        assert_eq!(Vec3::<f64>::ZERO.normalize_or_zero(), Vec3::ZERO);
+        assert_eq!(Vec3::<f64>::ZERO.normalize_or(Vec3::X), Vec3::X);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn vec_clamp_length() {
+        let v = Vec2::new(3.0, 4.0);
+        assert_eq!(v.clamp_length(1.0, 10.0), v);
+        assert_eq!(v.clamp_length(10.0, 20.0), Vec2::new(6.0, 8.0));
+        assert_eq!(v.clamp_length(0.0, 2.5), Vec2::new(1.5, 2.0));
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn vec_float_elementwise() {
+        let v = Vec4::new(-2.0, 0.5, 4.0, -0.25);
+        assert_eq!(v.abs(), Vec4::new(2.0, 0.5, 4.0, 0.25));
+        assert_eq!(v.signum(), Vec4::new(-1.0, 1.0, 1.0, -1.0));
+        assert_eq!(v.recip(), Vec4::new(-0.5, 2.0, 0.25, -4.0));
+        assert_eq!(
+            v.mul_add(Vec4::splat(2.0), Vec4::ONE),
+            Vec4::new(-3.0, 2.0, 9.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn vec_cmp_select() {
+        let a = Vec3::new(1, 5, 3);
+        let b = Vec3::new(2, 5, 1);
+        assert_eq!(a.cmplt(b), BVec3::new(true, false, false));
+        assert_eq!(a.cmple(b), BVec3::new(true, true, false));
+        assert_eq!(a.cmpgt(b), BVec3::new(false, false, true));
+        assert_eq!(a.cmpge(b), BVec3::new(false, true, true));
+        assert_eq!(a.cmpeq(b), BVec3::new(false, true, false));
+        assert_eq!(a.cmpne(b), BVec3::new(true, false, true));
+        assert_eq!(Vec3::select(a.cmplt(b), a, b), a.min(b));
+        assert!(
+            Vec2::new(f32::NAN, 0.0)
+                .cmpne(Vec2::new(f32::NAN, 0.0))
+                .any()
+        );
+    }
+
+    #[test]
+    fn vec_extend_truncate() {
+        assert_eq!(Vec2::new(1, 2).extend(3), Vec3::new(1, 2, 3));
+        assert_eq!(Vec3::new(1, 2, 3).extend(4), Vec4::new(1, 2, 3, 4));
+        assert_eq!(Vec3::new(1, 2, 3).truncate(), Vec2::new(1, 2));
+        assert_eq!(Vec4::new(1, 2, 3, 4).truncate(), Vec3::new(1, 2, 3));
+        assert_eq!(
+            Vec3::new(1, 2, 3).to_homogeneous_point(),
+            Vec4::new(1, 2, 3, 1)
+        );
+        assert_eq!(
+            Vec3::new(1, 2, 3).to_homogeneous_vector(),
+            Vec4::new(1, 2, 3, 0)
+        );
+        assert_eq!(
+            Vec4::new(2.0, 4.0, 6.0, 2.0).xyz_div_w(),
+            Vec3::new(1.0, 2.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn vec_casts() {
+        assert_eq!(Vec3::new(1.7, -2.2, 3.0).as_i32(), Vec3::new(1, -2, 3));
+        assert_eq!(Vec2::new(300_i32, -1).as_u8(), Vec2::new(44, 255));
+        assert_eq!(Vec2::new(1_u8, 2).as_u32(), Vec2::new(1, 2));
+        assert_eq!(Vec4::new(1.5_f32, 0.0, -1.0, 2.0).as_f64().x, 1.5);
+        assert_eq!(
+            Vec2::<u32>::try_from(Vec2::new(1_u8, 2)),
+            Ok(Vec2::new(1, 2))
+        );
+
+        assert_eq!(
+            Vec2::<f32>::try_from(Vec2::new(u128::MAX, 0)),
+            Err(TryCastError)
+        );
+
+        assert_eq!(
+            Vec2::<u32>::from_lossless(Vec2::new(1_u8, 2)),
+            Vec2::new(1, 2)
+        );
+        assert_eq!(
+            Vec3::<f64>::from_lossless(Vec3::new(1.5_f32, -2.0, 0.25)),
+            Vec3::new(1.5, -2.0, 0.25)
+        );
+
+        // Untyped literals still infer the element type of the other operand.
+        let d: Vec3<u32> = Vec3::splat(2);
+        assert_eq!(d + Vec3::new(1, 1, 1), Vec3::splat(3));
+        assert_eq!(d + 1, Vec3::splat(3));
+        assert_eq!(2 * d, Vec3::splat(4));
+
+        assert_eq!(
+            Vec2::<u8>::try_from(Vec2::new(1_i32, 255)),
+            Ok(Vec2::new(1, 255))
+        );
+        assert_eq!(
+            Vec2::<u8>::try_from(Vec2::new(1_i32, 256)),
+            Err(TryCastError)
+        );
+        assert_eq!(
+            Vec3::<i32>::try_from(Vec3::new(1.0, f32::NAN, 0.0)),
+            Err(TryCastError)
+        );
+        assert_eq!(
+            Vec2::<u32>::try_from(Vec2::new(f64::INFINITY, 0.0)),
+            Err(TryCastError)
+        );
+        assert_eq!(
+            Vec2::<f32>::try_from(Vec2::new(1e300_f64, 0.0)),
+            Err(TryCastError)
+        );
+    }
+
+    #[test]
+    fn vec_bitwise_ops() {
+        let a = Vec3::new(0b1100_u32, 0b1010, 0xff);
+        let b = Vec3::new(0b1010_u32, 0b0110, 0x0f);
+        assert_eq!(a & b, Vec3::new(0b1000, 0b0010, 0x0f));
+        assert_eq!(a | b, Vec3::new(0b1110, 0b1110, 0xff));
+        assert_eq!(a ^ b, Vec3::new(0b0110, 0b1100, 0xf0));
+        assert_eq!(!Vec2::new(0_u8, 0xf0), Vec2::new(0xff, 0x0f));
+        assert_eq!(a >> 1, Vec3::new(0b110, 0b101, 0x7f));
+        assert_eq!(Vec2::new(1_u32, 2) << Vec2::new(4, 1), Vec2::new(16, 4));
+        let (ra, rb) = (&a, &b);
+        assert_eq!(ra & rb, a & b);
+        assert_eq!(0xf0_u32 & a, Vec3::new(0, 0, 0xf0));
+        assert_eq!(1_u32 << Vec2::new(1_u32, 3), Vec2::new(2, 8));
+
+        let mut c = a;
+        c &= 0b1000;
+        c |= Vec3::new(1, 1, 1);
+        c <<= 1;
+        assert_eq!(c, Vec3::new(0b10010, 0b10010, 0b10010));
+    }
+
+    #[test]
+    fn vec_integer_ops() {
+        let a = Vec2::new(250_u8, 3);
+        let b = Vec2::new(10_u8, 5);
+        assert_eq!(a.wrapping_add(b), Vec2::new(4, 8));
+        assert_eq!(a.wrapping_sub(b), Vec2::new(240, 254));
+        assert_eq!(a.saturating_add(b), Vec2::new(255, 8));
+        assert_eq!(a.saturating_sub(b), Vec2::new(240, 0));
+        assert_eq!(a.saturating_mul(b), Vec2::new(255, 15));
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.checked_sub(Vec2::new(10, 3)), Some(Vec2::new(240, 0)));
+        assert_eq!(a.checked_div(Vec2::new(2, 0)), None);
+        assert_eq!(a.checked_rem(Vec2::new(7, 2)), Some(Vec2::new(5, 1)));
+
+        let c = Vec3::new(-7_i32, 7, -1);
+        assert_eq!(c.div_euclid(Vec3::splat(4)), Vec3::new(-2, 1, -1));
+        assert_eq!(c.rem_euclid(Vec3::splat(4)), Vec3::new(1, 3, 3));
+    }
+}